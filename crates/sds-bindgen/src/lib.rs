@@ -0,0 +1,69 @@
+//! # C headers for Rust functions that use SDS strings
+//!
+//! A thin layer over [cbindgen] that knows about the `sds` crate: `c_sds`
+//! parameters and return values come out as the `sds` typedef from `sds.h`
+//! (which the generated header includes) rather than as an unknown type or a
+//! plain `char *`.
+//!
+//! Use it from a build script:
+//!
+//! ```no_run
+//! let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! sds_bindgen::builder()
+//!     .with_crate(crate_dir)
+//!     .generate()
+//!     .expect("Unable to generate bindings")
+//!     .write_to_file(format!("{out_dir}/bindings.h"));
+//! ```
+//!
+//! or through the `sds-bindgen` binary, which takes the same options as a
+//! `cbindgen.toml` file.
+
+use std::path::Path;
+
+pub use cbindgen;
+use cbindgen::{Builder, Config, Language};
+
+/// The `sds` crate's name for the C `sds` type. `sds_sys::sds` needs no
+/// renaming.
+const C_SDS: &str = "c_sds";
+
+/// Adjusts `config` for functions that take or return SDS strings.
+///
+/// Includes `<sds.h>` and maps `sds::c_sds` to its `sds` typedef. Everything
+/// else in `config` is left alone, so this can be applied on top of a
+/// `cbindgen.toml`.
+pub fn configure(config: &mut Config) {
+    config.language = Language::C;
+    if !config.sys_includes.iter().any(|include| include == "sds.h") {
+        config.sys_includes.push("sds.h".to_string());
+    }
+    config
+        .export
+        .rename
+        .insert(C_SDS.to_string(), "sds".to_string());
+    config.usize_is_size_t = true;
+}
+
+/// Returns the default cbindgen configuration adjusted by [`configure`].
+pub fn config() -> Config {
+    let mut config = Config::default();
+    configure(&mut config);
+    config
+}
+
+/// Returns a [`cbindgen::Builder`] that uses [`config`].
+///
+/// Calling [`Builder::with_config`] on it replaces the configuration, pass
+/// the new one through [`configure`] first.
+pub fn builder() -> Builder {
+    Builder::new().with_config(config())
+}
+
+/// Reads a `cbindgen.toml` and adjusts it with [`configure`].
+pub fn config_from_file(path: impl AsRef<Path>) -> Result<Config, String> {
+    let mut config = Config::from_file(path)?;
+    configure(&mut config);
+    Ok(config)
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Generates a C header for the `extern \"C\"` functions of a crate, with `c_sds`
+spelled as the `sds` typedef from <sds.h>.
+
+Usage: sds-bindgen [--config <cbindgen.toml>] [--output <bindings.h>] [<crate-dir>]
+
+  <crate-dir>      The crate to generate bindings for [default: .]
+  --config <path>  cbindgen configuration [default: <crate-dir>/cbindgen.toml if it exists]
+  --output <path>  Where to write the header [default: stdout]";
+
+struct Args {
+    crate_dir: PathBuf,
+    config: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+/// Returns [`None`] if the usage was asked for.
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut args = Args {
+        crate_dir: PathBuf::from("."),
+        config: None,
+        output: None,
+    };
+    let mut argv = std::env::args_os().skip(1);
+    while let Some(arg) = argv.next() {
+        match arg.to_str() {
+            Some("-h" | "--help") => return Ok(None),
+            Some("-c" | "--config") => {
+                args.config = Some(argv.next().ok_or("--config needs a path")?.into());
+            }
+            Some("-o" | "--output") => {
+                args.output = Some(argv.next().ok_or("--output needs a path")?.into());
+            }
+            Some(flag) if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+            _ => args.crate_dir = arg.into(),
+        }
+    }
+    Ok(Some(args))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config_path = args
+        .config
+        .or_else(|| Some(args.crate_dir.join("cbindgen.toml")).filter(|path| path.exists()));
+    let config = match config_path {
+        Some(path) => match sds_bindgen::config_from_file(&path) {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => sds_bindgen::config(),
+    };
+
+    let bindings = match sds_bindgen::cbindgen::Builder::new()
+        .with_crate(&args.crate_dir)
+        .with_config(config)
+        .generate()
+    {
+        Ok(bindings) => bindings,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match args.output {
+        Some(path) => {
+            bindings.write_to_file(path);
+        }
+        None => bindings.write(std::io::stdout()),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+/// Generates a header for `src`, saved as `<name>.rs`.
+fn generate(name: &str, src: &str, config: sds_bindgen::cbindgen::Config) -> String {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sds-bindgen");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("{name}.rs"));
+    std::fs::write(&path, src).unwrap();
+
+    let mut header = Vec::new();
+    sds_bindgen::cbindgen::Builder::new()
+        .with_src(&path)
+        .with_config(config)
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    String::from_utf8(header).unwrap()
+}
+
+#[test]
+fn test_c_sds_is_sds() {
+    let header = generate(
+        "c_sds_is_sds",
+        r#"
+        use sds::{c_sds, SdsString};
+        use std::ffi::{c_char, c_int};
+
+        #[no_mangle]
+        pub extern "C" fn count_nuls(s: c_sds) -> c_int { 0 }
+
+        #[no_mangle]
+        pub extern "C" fn my_data(len: usize) -> sds::c_sds { todo!() }
+
+        #[no_mangle]
+        pub extern "C" fn split(s: *const c_char, count: *mut c_int) -> *mut c_sds { todo!() }
+        "#,
+        sds_bindgen::config(),
+    );
+    assert!(header.contains("#include <sds.h>\n"), "{header}");
+    assert!(header.contains("int count_nuls(sds s);"), "{header}");
+    assert!(header.contains("sds my_data(size_t len);"), "{header}");
+    assert!(
+        header.contains("sds *split(const char *s, int *count);"),
+        "{header}"
+    );
+    assert!(!header.contains("c_sds"), "{header}");
+}
+
+#[test]
+fn test_configure_keeps_settings() {
+    let mut config = sds_bindgen::cbindgen::Config {
+        include_guard: Some("MY_BINDINGS_H".to_string()),
+        sys_includes: vec!["stdio.h".to_string(), "sds.h".to_string()],
+        ..Default::default()
+    };
+    sds_bindgen::configure(&mut config);
+    let header = generate(
+        "configure_keeps_settings",
+        "#[no_mangle] pub extern \"C\" fn f(s: c_sds) {}",
+        config,
+    );
+    assert!(header.starts_with("#ifndef MY_BINDINGS_H"), "{header}");
+    assert_eq!(header.matches("#include <sds.h>").count(), 1, "{header}");
+    assert!(header.contains("#include <stdio.h>"), "{header}");
+    assert!(header.contains("void f(sds s);"), "{header}");
+}
@@ -0,0 +1,47 @@
+use std::env;
+use std::path::PathBuf;
+
+/// The functions from `src/sdsfmt.c`.
+const VARARGS_FUNCTIONS: [&str; 3] = ["sdscatvprintf", "sdscatprintf", "sdscatfmt"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/sdsfmt.c");
+    println!("cargo:rerun-if-changed=include/sds.h");
+
+    // Used by the tests to compile C programs for the same target.
+    println!("cargo:rustc-env=TARGET={}", env::var("TARGET").unwrap());
+
+    // Nothing on the Rust side calls into sdsfmt.c, without `+whole-archive`
+    // the linker would leave it out of the cdylib.
+    cc::Build::new()
+        .file("src/sdsfmt.c")
+        .include("include")
+        .link_lib_modifier("+whole-archive")
+        .compile("sdsfmt");
+
+    export_varargs_functions();
+}
+
+/// rustc only exports the `#[no_mangle]` functions of a cdylib and hides
+/// everything that comes from C, so the varargs functions have to be added to
+/// the exported symbols by hand.
+fn export_varargs_functions() {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    if target_os == "macos" || target_os == "ios" {
+        for function in VARARGS_FUNCTIONS {
+            println!("cargo:rustc-cdylib-link-arg=-Wl,-exported_symbol,_{function}");
+        }
+    } else if env::var("CARGO_CFG_TARGET_FAMILY").unwrap() == "unix" {
+        // Merged with the version script that rustc passes to the linker.
+        let script = PathBuf::from(env::var("OUT_DIR").unwrap()).join("sdsfmt.map");
+        std::fs::write(
+            &script,
+            format!("{{ global: {}; }};\n", VARARGS_FUNCTIONS.join("; ")),
+        )
+        .unwrap();
+        println!(
+            "cargo:rustc-cdylib-link-arg=-Wl,--version-script={}",
+            script.display()
+        );
+    }
+}
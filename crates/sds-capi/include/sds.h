@@ -0,0 +1,336 @@
+#ifndef __SDS_H
+#define __SDS_H
+
+/* Generated by cbindgen from crates/sds-capi, do not edit. */
+
+#include <stdarg.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/types.h>
+
+#define SDS_MAX_PREALLOC (1024 * 1024)
+
+#define SDS_TYPE_5 0
+
+#define SDS_TYPE_8 1
+
+#define SDS_TYPE_16 2
+
+#define SDS_TYPE_32 3
+
+#define SDS_TYPE_64 4
+
+#define SDS_TYPE_MASK 7
+
+#define SDS_TYPE_BITS 3
+
+typedef char *sds;
+
+/**
+ * Note: sdshdr5 is never used, we just access the flags byte directly.
+ * However is here to document the layout of type 5 SDS strings.
+ */
+typedef struct __attribute__((__packed__)) sdshdr5 {
+  /**
+   * 3 lsb of type, and 5 msb of string length
+   */
+  unsigned char flags;
+  char buf[0];
+} sdshdr5;
+
+typedef struct __attribute__((__packed__)) sdshdr8 {
+  /**
+   * used
+   */
+  uint8_t len;
+  /**
+   * excluding the header and null terminator
+   */
+  uint8_t alloc;
+  /**
+   * 3 lsb of type, 5 unused bits
+   */
+  unsigned char flags;
+  char buf[0];
+} sdshdr8;
+
+typedef struct __attribute__((__packed__)) sdshdr16 {
+  /**
+   * used
+   */
+  uint16_t len;
+  /**
+   * excluding the header and null terminator
+   */
+  uint16_t alloc;
+  /**
+   * 3 lsb of type, 5 unused bits
+   */
+  unsigned char flags;
+  char buf[0];
+} sdshdr16;
+
+typedef struct __attribute__((__packed__)) sdshdr32 {
+  /**
+   * used
+   */
+  uint32_t len;
+  /**
+   * excluding the header and null terminator
+   */
+  uint32_t alloc;
+  /**
+   * 3 lsb of type, 5 unused bits
+   */
+  unsigned char flags;
+  char buf[0];
+} sdshdr32;
+
+typedef struct __attribute__((__packed__)) sdshdr64 {
+  /**
+   * used
+   */
+  uint64_t len;
+  /**
+   * excluding the header and null terminator
+   */
+  uint64_t alloc;
+  /**
+   * 3 lsb of type, 5 unused bits
+   */
+  unsigned char flags;
+  char buf[0];
+} sdshdr64;
+
+/**
+ * Returns the length of `s` in bytes.
+ */
+size_t sdslen(sds s);
+
+/**
+ * Returns the free space at the end of `s`.
+ */
+size_t sdsavail(sds s);
+
+/**
+ * Sets the length stored in the header of `s`.
+ */
+void sdssetlen(sds s, size_t newlen);
+
+/**
+ * Adds `inc` to the length stored in the header of `s`.
+ */
+void sdsinclen(sds s, size_t inc);
+
+/**
+ * Returns the capacity of `s`: `sdsavail(s) + sdslen(s)`.
+ */
+size_t sdsalloc(sds s);
+
+/**
+ * Sets the capacity stored in the header of `s`.
+ */
+void sdssetalloc(sds s, size_t newlen);
+
+/**
+ * Creates a string from `initlen` bytes at `init`, or zeroed bytes if `init`
+ * is NULL.
+ */
+sds sdsnewlen(const void *init, size_t initlen);
+
+/**
+ * Creates a string from a nul-terminated C string.
+ */
+sds sdsnew(const char *init);
+
+/**
+ * Creates an empty string.
+ */
+sds sdsempty(void);
+
+/**
+ * Duplicates `s`.
+ */
+sds sdsdup(sds s);
+
+/**
+ * Frees `s`. Does nothing if `s` is NULL.
+ */
+void sdsfree(sds s);
+
+/**
+ * Grows `s` to `len` bytes, filling the new bytes with zeroes.
+ */
+sds sdsgrowzero(sds s, size_t len);
+
+/**
+ * Appends `len` bytes at `t` to `s`.
+ */
+sds sdscatlen(sds s, const void *t, size_t len);
+
+/**
+ * Appends the nul-terminated C string `t` to `s`.
+ */
+sds sdscat(sds s, const char *t);
+
+/**
+ * Appends the string `t` to `s`.
+ */
+sds sdscatsds(sds s, sds t);
+
+/**
+ * Replaces the contents of `s` with `len` bytes at `t`.
+ */
+sds sdscpylen(sds s, const char *t, size_t len);
+
+/**
+ * Replaces the contents of `s` with the nul-terminated C string `t`.
+ */
+sds sdscpy(sds s, const char *t);
+
+/**
+ * Removes all bytes in `cset` from both ends of `s`.
+ */
+sds sdstrim(sds s, const char *cset);
+
+/**
+ * Keeps `len` bytes of `s` starting at `start`.
+ */
+void sdssubstr(sds s, size_t start, size_t len);
+
+/**
+ * Keeps the bytes of `s` from `start` to `end` (inclusive), counting from the
+ * end for negative indices.
+ */
+void sdsrange(sds s, ssize_t start, ssize_t end);
+
+/**
+ * Sets the length of `s` to the position of its first nul byte.
+ */
+void sdsupdatelen(sds s);
+
+/**
+ * Sets the length of `s` to zero, keeping its buffer.
+ */
+void sdsclear(sds s);
+
+/**
+ * Compares `s1` and `s2` with `memcmp`, the shorter one first if one is a
+ * prefix of the other.
+ */
+int sdscmp(sds s1, sds s2);
+
+/**
+ * Splits `len` bytes at `s` on the `seplen` bytes separator `sep`. The number
+ * of tokens is stored in `count`, free the result with `sdsfreesplitres`.
+ */
+sds *sdssplitlen(const char *s, ssize_t len, const char *sep, int seplen, int *count);
+
+/**
+ * Frees the result of `sdssplitlen` or `sdssplitargs`.
+ */
+void sdsfreesplitres(sds *tokens, int count);
+
+/**
+ * Turns `s` to lower case.
+ */
+void sdstolower(sds s);
+
+/**
+ * Turns `s` to upper case.
+ */
+void sdstoupper(sds s);
+
+/**
+ * Creates a string holding the decimal representation of `value`.
+ */
+sds sdsfromlonglong(long long value);
+
+/**
+ * Appends an escaped, quoted representation of `len` bytes at `p` to `s`.
+ */
+sds sdscatrepr(sds s, const char *p, size_t len);
+
+/**
+ * Splits a line into arguments like a shell or the Redis CLI would. Returns
+ * NULL on unbalanced quotes.
+ */
+sds *sdssplitargs(const char *line, int *argc);
+
+/**
+ * Replaces every byte of `s` that appears in `from` with the byte at the same
+ * position in `to`.
+ */
+sds sdsmapchars(sds s, const char *from, const char *to, size_t setlen);
+
+/**
+ * Joins `argc` C strings with the separator `sep`.
+ */
+sds sdsjoin(char **argv, int argc, char *sep);
+
+/**
+ * Joins `argc` strings with the `seplen` bytes separator `sep`.
+ */
+sds sdsjoinsds(sds *argv, int argc, const char *sep, size_t seplen);
+
+/**
+ * Returns 1 if `s` would be escaped by `sdscatrepr`, 0 otherwise.
+ */
+int sdsneedsrepr(sds s);
+
+/**
+ * Makes sure that `s` has room for at least `addlen` more bytes.
+ */
+sds sdsMakeRoomFor(sds s, size_t addlen);
+
+/**
+ * Adjusts the length of `s` after writing to (or truncating) its free space.
+ */
+void sdsIncrLen(sds s, ssize_t incr);
+
+/**
+ * Reallocates `s` without any free space at the end.
+ */
+sds sdsRemoveFreeSpace(sds s);
+
+/**
+ * Reallocates `s` to a capacity of exactly `size` bytes, truncating it if
+ * needed.
+ */
+sds sdsResize(sds s, size_t size);
+
+/**
+ * Returns the size of the whole allocation of `s`, header included.
+ */
+size_t sdsAllocSize(sds s);
+
+/**
+ * Returns the start of the allocation of `s`, that is its header.
+ */
+void *sdsAllocPtr(sds s);
+
+/**
+ * Allocates with the same allocator as the strings.
+ */
+void *sds_malloc(size_t size);
+
+/**
+ * Reallocates with the same allocator as the strings.
+ */
+void *sds_realloc(void *ptr, size_t size);
+
+/**
+ * Frees with the same allocator as the strings.
+ */
+void sds_free(void *ptr);
+
+sds sdscatvprintf(sds s, const char *fmt, va_list ap);
+#ifdef __GNUC__
+sds sdscatprintf(sds s, const char *fmt, ...)
+    __attribute__((format(printf, 2, 3)));
+#else
+sds sdscatprintf(sds s, const char *fmt, ...);
+#endif
+sds sdscatfmt(sds s, char const *fmt, ...);
+
+#endif /* __SDS_H */
@@ -0,0 +1,326 @@
+//! # libsds implemented in Rust
+//!
+//! Exports every function of `sds.h` under its original, unmangled name so
+//! that this crate, built as `libsds.so` or `libsds.a`, is a drop-in
+//! replacement for the C library. The string handling itself is the Rust port
+//! from [`sds_pure`].
+//!
+//! # Functions that stay in C
+//!
+//! `sdscatvprintf`, `sdscatprintf` and `sdscatfmt` take C varargs, which
+//! stable Rust can't define. They are still C, compiled from `src/sdsfmt.c`
+//! with the `cc` crate, so building this crate needs a C compiler. They only
+//! format into a buffer and then call the Rust implementations of
+//! `sdsMakeRoomFor`, `sdscatlen` and the like through `sds.h`.
+//!
+//! C code includes `include/sds.h`, which is generated from this file with
+//! cbindgen and checked by `tests/header.rs`.
+
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, c_int, c_longlong, c_void};
+
+pub use sds_pure::{sds, sdshdr16, sdshdr32, sdshdr5, sdshdr64, sdshdr8, ssize_t};
+
+// Spelled out rather than re-exported so that cbindgen picks them up.
+pub const SDS_MAX_PREALLOC: u32 = 1024 * 1024;
+pub const SDS_TYPE_5: u32 = 0;
+pub const SDS_TYPE_8: u32 = 1;
+pub const SDS_TYPE_16: u32 = 2;
+pub const SDS_TYPE_32: u32 = 3;
+pub const SDS_TYPE_64: u32 = 4;
+pub const SDS_TYPE_MASK: u32 = 7;
+pub const SDS_TYPE_BITS: u32 = 3;
+
+const _: () = assert!(
+    SDS_MAX_PREALLOC == sds_pure::SDS_MAX_PREALLOC
+        && SDS_TYPE_5 == sds_pure::SDS_TYPE_5
+        && SDS_TYPE_8 == sds_pure::SDS_TYPE_8
+        && SDS_TYPE_16 == sds_pure::SDS_TYPE_16
+        && SDS_TYPE_32 == sds_pure::SDS_TYPE_32
+        && SDS_TYPE_64 == sds_pure::SDS_TYPE_64
+        && SDS_TYPE_MASK == sds_pure::SDS_TYPE_MASK
+        && SDS_TYPE_BITS == sds_pure::SDS_TYPE_BITS
+);
+
+/// Returns the length of `s` in bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sdslen(s: sds) -> usize {
+    sds_pure::sdslen(s)
+}
+
+/// Returns the free space at the end of `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsavail(s: sds) -> usize {
+    sds_pure::sdsavail(s)
+}
+
+/// Sets the length stored in the header of `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdssetlen(s: sds, newlen: usize) {
+    sds_pure::sdssetlen(s, newlen)
+}
+
+/// Adds `inc` to the length stored in the header of `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsinclen(s: sds, inc: usize) {
+    sds_pure::sdsinclen(s, inc)
+}
+
+/// Returns the capacity of `s`: `sdsavail(s) + sdslen(s)`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsalloc(s: sds) -> usize {
+    sds_pure::sdsalloc(s)
+}
+
+/// Sets the capacity stored in the header of `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdssetalloc(s: sds, newlen: usize) {
+    sds_pure::sdssetalloc(s, newlen)
+}
+
+/// Creates a string from `initlen` bytes at `init`, or zeroed bytes if `init`
+/// is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn sdsnewlen(init: *const c_void, initlen: usize) -> sds {
+    sds_pure::sdsnewlen(init, initlen)
+}
+
+/// Creates a string from a nul-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn sdsnew(init: *const c_char) -> sds {
+    sds_pure::sdsnew(init)
+}
+
+/// Creates an empty string.
+#[no_mangle]
+pub unsafe extern "C" fn sdsempty() -> sds {
+    sds_pure::sdsempty()
+}
+
+/// Duplicates `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsdup(s: sds) -> sds {
+    sds_pure::sdsdup(s)
+}
+
+/// Frees `s`. Does nothing if `s` is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn sdsfree(s: sds) {
+    sds_pure::sdsfree(s)
+}
+
+/// Grows `s` to `len` bytes, filling the new bytes with zeroes.
+#[no_mangle]
+pub unsafe extern "C" fn sdsgrowzero(s: sds, len: usize) -> sds {
+    sds_pure::sdsgrowzero(s, len)
+}
+
+/// Appends `len` bytes at `t` to `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscatlen(s: sds, t: *const c_void, len: usize) -> sds {
+    sds_pure::sdscatlen(s, t, len)
+}
+
+/// Appends the nul-terminated C string `t` to `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscat(s: sds, t: *const c_char) -> sds {
+    sds_pure::sdscat(s, t)
+}
+
+/// Appends the string `t` to `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscatsds(s: sds, t: sds) -> sds {
+    sds_pure::sdscatsds(s, t)
+}
+
+/// Replaces the contents of `s` with `len` bytes at `t`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscpylen(s: sds, t: *const c_char, len: usize) -> sds {
+    sds_pure::sdscpylen(s, t, len)
+}
+
+/// Replaces the contents of `s` with the nul-terminated C string `t`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscpy(s: sds, t: *const c_char) -> sds {
+    sds_pure::sdscpy(s, t)
+}
+
+/// Removes all bytes in `cset` from both ends of `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdstrim(s: sds, cset: *const c_char) -> sds {
+    sds_pure::sdstrim(s, cset)
+}
+
+/// Keeps `len` bytes of `s` starting at `start`.
+#[no_mangle]
+pub unsafe extern "C" fn sdssubstr(s: sds, start: usize, len: usize) {
+    sds_pure::sdssubstr(s, start, len)
+}
+
+/// Keeps the bytes of `s` from `start` to `end` (inclusive), counting from the
+/// end for negative indices.
+#[no_mangle]
+pub unsafe extern "C" fn sdsrange(s: sds, start: ssize_t, end: ssize_t) {
+    sds_pure::sdsrange(s, start, end)
+}
+
+/// Sets the length of `s` to the position of its first nul byte.
+#[no_mangle]
+pub unsafe extern "C" fn sdsupdatelen(s: sds) {
+    sds_pure::sdsupdatelen(s)
+}
+
+/// Sets the length of `s` to zero, keeping its buffer.
+#[no_mangle]
+pub unsafe extern "C" fn sdsclear(s: sds) {
+    sds_pure::sdsclear(s)
+}
+
+/// Compares `s1` and `s2` with `memcmp`, the shorter one first if one is a
+/// prefix of the other.
+#[no_mangle]
+pub unsafe extern "C" fn sdscmp(s1: sds, s2: sds) -> c_int {
+    sds_pure::sdscmp(s1, s2)
+}
+
+/// Splits `len` bytes at `s` on the `seplen` bytes separator `sep`. The number
+/// of tokens is stored in `count`, free the result with `sdsfreesplitres`.
+#[no_mangle]
+pub unsafe extern "C" fn sdssplitlen(
+    s: *const c_char,
+    len: ssize_t,
+    sep: *const c_char,
+    seplen: c_int,
+    count: *mut c_int,
+) -> *mut sds {
+    sds_pure::sdssplitlen(s, len, sep, seplen, count)
+}
+
+/// Frees the result of `sdssplitlen` or `sdssplitargs`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsfreesplitres(tokens: *mut sds, count: c_int) {
+    sds_pure::sdsfreesplitres(tokens, count)
+}
+
+/// Turns `s` to lower case.
+#[no_mangle]
+pub unsafe extern "C" fn sdstolower(s: sds) {
+    sds_pure::sdstolower(s)
+}
+
+/// Turns `s` to upper case.
+#[no_mangle]
+pub unsafe extern "C" fn sdstoupper(s: sds) {
+    sds_pure::sdstoupper(s)
+}
+
+/// Creates a string holding the decimal representation of `value`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsfromlonglong(value: c_longlong) -> sds {
+    sds_pure::sdsfromlonglong(value)
+}
+
+/// Appends an escaped, quoted representation of `len` bytes at `p` to `s`.
+#[no_mangle]
+pub unsafe extern "C" fn sdscatrepr(s: sds, p: *const c_char, len: usize) -> sds {
+    sds_pure::sdscatrepr(s, p, len)
+}
+
+/// Splits a line into arguments like a shell or the Redis CLI would. Returns
+/// NULL on unbalanced quotes.
+#[no_mangle]
+pub unsafe extern "C" fn sdssplitargs(line: *const c_char, argc: *mut c_int) -> *mut sds {
+    sds_pure::sdssplitargs(line, argc)
+}
+
+/// Replaces every byte of `s` that appears in `from` with the byte at the same
+/// position in `to`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsmapchars(
+    s: sds,
+    from: *const c_char,
+    to: *const c_char,
+    setlen: usize,
+) -> sds {
+    sds_pure::sdsmapchars(s, from, to, setlen)
+}
+
+/// Joins `argc` C strings with the separator `sep`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsjoin(argv: *mut *mut c_char, argc: c_int, sep: *mut c_char) -> sds {
+    sds_pure::sdsjoin(argv, argc, sep)
+}
+
+/// Joins `argc` strings with the `seplen` bytes separator `sep`.
+#[no_mangle]
+pub unsafe extern "C" fn sdsjoinsds(
+    argv: *mut sds,
+    argc: c_int,
+    sep: *const c_char,
+    seplen: usize,
+) -> sds {
+    sds_pure::sdsjoinsds(argv, argc, sep, seplen)
+}
+
+/// Returns 1 if `s` would be escaped by `sdscatrepr`, 0 otherwise.
+#[no_mangle]
+pub unsafe extern "C" fn sdsneedsrepr(s: sds) -> c_int {
+    sds_pure::sdsneedsrepr(s)
+}
+
+/// Makes sure that `s` has room for at least `addlen` more bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sdsMakeRoomFor(s: sds, addlen: usize) -> sds {
+    sds_pure::sdsMakeRoomFor(s, addlen)
+}
+
+/// Adjusts the length of `s` after writing to (or truncating) its free space.
+#[no_mangle]
+pub unsafe extern "C" fn sdsIncrLen(s: sds, incr: ssize_t) {
+    sds_pure::sdsIncrLen(s, incr)
+}
+
+/// Reallocates `s` without any free space at the end.
+#[no_mangle]
+pub unsafe extern "C" fn sdsRemoveFreeSpace(s: sds) -> sds {
+    sds_pure::sdsRemoveFreeSpace(s)
+}
+
+/// Reallocates `s` to a capacity of exactly `size` bytes, truncating it if
+/// needed.
+#[no_mangle]
+pub unsafe extern "C" fn sdsResize(s: sds, size: usize) -> sds {
+    sds_pure::sdsResize(s, size)
+}
+
+/// Returns the size of the whole allocation of `s`, header included.
+#[no_mangle]
+pub unsafe extern "C" fn sdsAllocSize(s: sds) -> usize {
+    sds_pure::sdsAllocSize(s)
+}
+
+/// Returns the start of the allocation of `s`, that is its header.
+#[no_mangle]
+pub unsafe extern "C" fn sdsAllocPtr(s: sds) -> *mut c_void {
+    sds_pure::sdsAllocPtr(s)
+}
+
+/// Allocates with the same allocator as the strings.
+#[no_mangle]
+pub unsafe extern "C" fn sds_malloc(size: usize) -> *mut c_void {
+    sds_pure::sds_malloc(size)
+}
+
+/// Reallocates with the same allocator as the strings.
+#[no_mangle]
+pub unsafe extern "C" fn sds_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    sds_pure::sds_realloc(ptr, size)
+}
+
+/// Frees with the same allocator as the strings.
+#[no_mangle]
+pub unsafe extern "C" fn sds_free(ptr: *mut c_void) {
+    sds_pure::sds_free(ptr)
+}
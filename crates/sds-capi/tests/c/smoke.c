@@ -0,0 +1,37 @@
+#include <stdio.h>
+
+#include "sds.h"
+
+int main(void) {
+    sds s = sdsnew("Hello");
+    s = sdscat(s, " World");
+    printf("%s %zu\n", s, sdslen(s));
+
+    s = sdscatprintf(s, " %d+%s", 42, "printf");
+    sds t = sdsnewlen("sds\0", 4);
+    s = sdscatfmt(s, " %i %I %u %U %S %s %%", -1, -9000000000LL, 7u, 18446744073709551615ULL, t, "c");
+    sdsfree(t);
+    fwrite(s, 1, sdslen(s), stdout);
+    putchar('\n');
+
+    int count;
+    sds *tokens = sdssplitlen("a,b,,c", 6, ",", 1, &count);
+    sds joined = sdsjoinsds(tokens, count, "|", 1);
+    printf("%d %s\n", count, joined);
+    sdsfreesplitres(tokens, count);
+    sdsfree(joined);
+
+    sds *argv = sdssplitargs("set \"key\\x41\" 'value'", &count);
+    joined = sdsjoin(argv, count, "/");
+    printf("%d %s\n", count, joined);
+    sdsfreesplitres(argv, count);
+    sdsfree(joined);
+
+    sdsrange(s, 0, 4);
+    sdstoupper(s);
+    s = sdscatrepr(s, "\n\0", 2);
+    printf("%s\n", s);
+
+    sdsfree(s);
+    return 0;
+}
@@ -0,0 +1,64 @@
+//! Compiles `tests/c/*.c` against `include/sds.h` and `libsds.a` and checks
+//! what the programs print.
+#![cfg(target_os = "linux")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds `libsds.a` and returns its path.
+///
+/// Cargo doesn't build the staticlib of a package for its own integration
+/// tests (only an rlib, which would collide with the `sds` crate), so this
+/// runs a nested cargo with a target directory of its own.
+fn staticlib() -> PathBuf {
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sds-capi");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .args(["--target", env!("TARGET"), "--target-dir"])
+        .arg(&target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to build libsds.a");
+    target_dir.join(env!("TARGET")).join("debug/libsds.a")
+}
+
+/// Links `tests/c/<name>.c` with the staticlib and returns its stdout.
+fn run(name: &str) -> String {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let exe = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("TARGET"))
+        .opt_level(0)
+        .cargo_metadata(false)
+        .get_compiler();
+    let status = compiler
+        .to_command()
+        .arg(crate_dir.join("tests/c").join(format!("{name}.c")))
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg(staticlib())
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&exe)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to compile {name}.c");
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(output.status.success(), "{name} failed: {output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_smoke() {
+    assert_eq!(
+        run("smoke"),
+        "Hello World 11\n\
+         Hello World 42+printf -1 -9000000000 7 18446744073709551615 sds\0 c %\n\
+         4 a|b||c\n\
+         3 set/keyA/value\n\
+         HELLO\"\\n\\x00\"\n"
+    );
+}
@@ -0,0 +1,27 @@
+//! Checks that the checked-in `include/sds.h` matches what cbindgen generates
+//! from the exported functions. Set `UPDATE_HEADER=1` to rewrite it.
+
+use std::path::Path;
+
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the header")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let header = crate_dir.join("include/sds.h");
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&header, &generated).unwrap();
+    }
+    assert!(
+        std::fs::read_to_string(&header).unwrap() == generated,
+        "include/sds.h is out of date, rerun with UPDATE_HEADER=1"
+    );
+}
@@ -0,0 +1,69 @@
+//! Allocation functions used by this crate and by the `rust-alloc` build of
+//! `sds.c` instead of libc `malloc`, `realloc` and `free` (see
+//! `sds-sys/src/sdsalloc.h`).
+//!
+//! Rust's allocator needs the [`Layout`] of a block to resize or free it while
+//! C only passes the pointer around, so every block is prefixed with a small
+//! header that records the requested size.
+
+use std::alloc::{self, Layout};
+use std::ffi::c_void;
+use std::ptr;
+
+/// Alignment of the returned pointers. Matches what glibc `malloc` guarantees
+/// on 64-bit targets, which is what `sds.c` was written against.
+const ALIGN: usize = 16;
+
+/// Bytes in front of every block. Holds the requested size as a `usize` and is
+/// padded to [`ALIGN`] so that the pointer handed to C stays aligned.
+const HEADER: usize = ALIGN;
+
+fn layout(size: usize) -> Option<Layout> {
+    Layout::from_size_align(size.checked_add(HEADER)?, ALIGN).ok()
+}
+
+/// Returns the start of the block and the layout it was allocated with.
+unsafe fn block(ptr: *mut c_void) -> (*mut u8, Layout) {
+    let base = ptr.cast::<u8>().sub(HEADER);
+    let size = base.cast::<usize>().read();
+    (base, layout(size).unwrap())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sds_rust_malloc(size: usize) -> *mut c_void {
+    let Some(layout) = layout(size) else {
+        return ptr::null_mut();
+    };
+    let base = alloc::alloc(layout);
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+    base.cast::<usize>().write(size);
+    base.add(HEADER).cast()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sds_rust_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    if ptr.is_null() {
+        return sds_rust_malloc(size);
+    }
+    let Some(new_layout) = layout(size) else {
+        return ptr::null_mut();
+    };
+    let (base, old_layout) = block(ptr);
+    let base = alloc::realloc(base, old_layout, new_layout.size());
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+    base.cast::<usize>().write(size);
+    base.add(HEADER).cast()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sds_rust_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    let (base, layout) = block(ptr);
+    alloc::dealloc(base, layout);
+}
@@ -0,0 +1,1209 @@
+//! # Simple Dynamic Strings in Rust
+//!
+//! A Rust port of `sds.c`. Every function has the same name, signature and behavior as its C
+//! counterpart and the string headers are laid out bit for bit like
+//! `struct sdshdr5`..`struct sdshdr64`, so a string created here can be passed
+//! to a C libsds (and the other way around) as long as both sides share the
+//! same `malloc`. That is libc `malloc` by default and the Rust global
+//! allocator with the `rust-alloc` feature, matching what the vendored `sds.c`
+//! is built with.
+//!
+//! `sds-sys` re-exports this crate as `sds_sys::pure`, and from its crate root
+//! instead of the C bindings with its `pure-rust` feature. `sds-capi` exports
+//! it to C. The varargs functions (`sdscatprintf`, `sdscatvprintf` and
+//! `sdscatfmt`) can't be written in stable Rust and are missing.
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, c_int, c_longlong, c_uchar, c_void, CStr};
+use std::ptr;
+
+#[cfg(feature = "rust-alloc")]
+mod alloc;
+
+#[cfg(feature = "rust-alloc")]
+use crate::alloc::{sds_rust_free as free, sds_rust_malloc as malloc, sds_rust_realloc as realloc};
+
+#[cfg(not(feature = "rust-alloc"))]
+extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+    fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+}
+
+pub const SDS_MAX_PREALLOC: u32 = 1024 * 1024;
+pub const SDS_TYPE_5: u32 = 0;
+pub const SDS_TYPE_8: u32 = 1;
+pub const SDS_TYPE_16: u32 = 2;
+pub const SDS_TYPE_32: u32 = 3;
+pub const SDS_TYPE_64: u32 = 4;
+pub const SDS_TYPE_MASK: u32 = 7;
+pub const SDS_TYPE_BITS: u32 = 3;
+pub type ssize_t = isize;
+pub type sds = *mut c_char;
+
+/// Note: sdshdr5 is never used, we just access the flags byte directly.
+/// However is here to document the layout of type 5 SDS strings.
+#[repr(C, packed)]
+pub struct sdshdr5 {
+    /// 3 lsb of type, and 5 msb of string length
+    pub flags: c_uchar,
+    pub buf: [c_char; 0],
+}
+
+#[repr(C, packed)]
+pub struct sdshdr8 {
+    /// used
+    pub len: u8,
+    /// excluding the header and null terminator
+    pub alloc: u8,
+    /// 3 lsb of type, 5 unused bits
+    pub flags: c_uchar,
+    pub buf: [c_char; 0],
+}
+
+#[repr(C, packed)]
+pub struct sdshdr16 {
+    /// used
+    pub len: u16,
+    /// excluding the header and null terminator
+    pub alloc: u16,
+    /// 3 lsb of type, 5 unused bits
+    pub flags: c_uchar,
+    pub buf: [c_char; 0],
+}
+
+#[repr(C, packed)]
+pub struct sdshdr32 {
+    /// used
+    pub len: u32,
+    /// excluding the header and null terminator
+    pub alloc: u32,
+    /// 3 lsb of type, 5 unused bits
+    pub flags: c_uchar,
+    pub buf: [c_char; 0],
+}
+
+#[repr(C, packed)]
+pub struct sdshdr64 {
+    /// used
+    pub len: u64,
+    /// excluding the header and null terminator
+    pub alloc: u64,
+    /// 3 lsb of type, 5 unused bits
+    pub flags: c_uchar,
+    pub buf: [c_char; 0],
+}
+
+const TYPE_5: u8 = SDS_TYPE_5 as u8;
+const TYPE_8: u8 = SDS_TYPE_8 as u8;
+const TYPE_16: u8 = SDS_TYPE_16 as u8;
+const TYPE_32: u8 = SDS_TYPE_32 as u8;
+const TYPE_64: u8 = SDS_TYPE_64 as u8;
+const TYPE_MASK: u8 = SDS_TYPE_MASK as u8;
+
+/// `SDS_HDR(T, s)`: pointer to the header of `s`.
+macro_rules! hdr {
+    ($hdr:ty, $s:expr) => {
+        $s.sub(std::mem::size_of::<$hdr>()).cast::<$hdr>()
+    };
+}
+
+unsafe fn flags(s: sds) -> u8 {
+    *s.sub(1) as u8
+}
+
+fn hdr_size(flags: u8) -> usize {
+    match flags & TYPE_MASK {
+        TYPE_5 => std::mem::size_of::<sdshdr5>(),
+        TYPE_8 => std::mem::size_of::<sdshdr8>(),
+        TYPE_16 => std::mem::size_of::<sdshdr16>(),
+        TYPE_32 => std::mem::size_of::<sdshdr32>(),
+        TYPE_64 => std::mem::size_of::<sdshdr64>(),
+        _ => 0,
+    }
+}
+
+fn req_type(string_size: usize) -> u8 {
+    if string_size < 1 << 5 {
+        TYPE_5
+    } else if string_size < 1 << 8 {
+        TYPE_8
+    } else if string_size < 1 << 16 {
+        TYPE_16
+    } else if cfg!(target_pointer_width = "64") && (string_size as u64) < 1 << 32 {
+        TYPE_32
+    } else if cfg!(target_pointer_width = "64") {
+        TYPE_64
+    } else {
+        TYPE_32
+    }
+}
+
+pub unsafe extern "C" fn sdslen(s: sds) -> usize {
+    let flags = flags(s);
+    match flags & TYPE_MASK {
+        TYPE_5 => (flags >> SDS_TYPE_BITS) as usize,
+        TYPE_8 => (*hdr!(sdshdr8, s)).len as usize,
+        TYPE_16 => (*hdr!(sdshdr16, s)).len as usize,
+        TYPE_32 => (*hdr!(sdshdr32, s)).len as usize,
+        TYPE_64 => (*hdr!(sdshdr64, s)).len as usize,
+        _ => 0,
+    }
+}
+
+pub unsafe extern "C" fn sdsavail(s: sds) -> usize {
+    match flags(s) & TYPE_MASK {
+        TYPE_8 => {
+            let sh = hdr!(sdshdr8, s);
+            ((*sh).alloc - (*sh).len) as usize
+        }
+        TYPE_16 => {
+            let sh = hdr!(sdshdr16, s);
+            ((*sh).alloc - (*sh).len) as usize
+        }
+        TYPE_32 => {
+            let sh = hdr!(sdshdr32, s);
+            ((*sh).alloc - (*sh).len) as usize
+        }
+        TYPE_64 => {
+            let sh = hdr!(sdshdr64, s);
+            ((*sh).alloc - (*sh).len) as usize
+        }
+        _ => 0,
+    }
+}
+
+pub unsafe extern "C" fn sdssetlen(s: sds, newlen: usize) {
+    match flags(s) & TYPE_MASK {
+        TYPE_5 => *s.sub(1) = (TYPE_5 | ((newlen as u8) << SDS_TYPE_BITS)) as c_char,
+        TYPE_8 => (*hdr!(sdshdr8, s)).len = newlen as u8,
+        TYPE_16 => (*hdr!(sdshdr16, s)).len = newlen as u16,
+        TYPE_32 => (*hdr!(sdshdr32, s)).len = newlen as u32,
+        TYPE_64 => (*hdr!(sdshdr64, s)).len = newlen as u64,
+        _ => {}
+    }
+}
+
+pub unsafe extern "C" fn sdsinclen(s: sds, inc: usize) {
+    let flags = flags(s);
+    match flags & TYPE_MASK {
+        TYPE_5 => {
+            let newlen = (flags >> SDS_TYPE_BITS) as usize + inc;
+            *s.sub(1) = (TYPE_5 | ((newlen as u8) << SDS_TYPE_BITS)) as c_char;
+        }
+        TYPE_8 => (*hdr!(sdshdr8, s)).len += inc as u8,
+        TYPE_16 => (*hdr!(sdshdr16, s)).len += inc as u16,
+        TYPE_32 => (*hdr!(sdshdr32, s)).len += inc as u32,
+        TYPE_64 => (*hdr!(sdshdr64, s)).len += inc as u64,
+        _ => {}
+    }
+}
+
+/// sdsalloc() = sdsavail() + sdslen()
+pub unsafe extern "C" fn sdsalloc(s: sds) -> usize {
+    let flags = flags(s);
+    match flags & TYPE_MASK {
+        TYPE_5 => (flags >> SDS_TYPE_BITS) as usize,
+        TYPE_8 => (*hdr!(sdshdr8, s)).alloc as usize,
+        TYPE_16 => (*hdr!(sdshdr16, s)).alloc as usize,
+        TYPE_32 => (*hdr!(sdshdr32, s)).alloc as usize,
+        TYPE_64 => (*hdr!(sdshdr64, s)).alloc as usize,
+        _ => 0,
+    }
+}
+
+pub unsafe extern "C" fn sdssetalloc(s: sds, newlen: usize) {
+    match flags(s) & TYPE_MASK {
+        // Nothing to do, this type has no total allocation info.
+        TYPE_5 => {}
+        TYPE_8 => (*hdr!(sdshdr8, s)).alloc = newlen as u8,
+        TYPE_16 => (*hdr!(sdshdr16, s)).alloc = newlen as u16,
+        TYPE_32 => (*hdr!(sdshdr32, s)).alloc = newlen as u32,
+        TYPE_64 => (*hdr!(sdshdr64, s)).alloc = newlen as u64,
+        _ => {}
+    }
+}
+
+/// Create a new sds string with the content specified by the 'init' pointer
+/// and 'initlen'. If NULL is used for 'init' the string is initialized with
+/// zero bytes.
+pub unsafe extern "C" fn sdsnewlen(init: *const c_void, initlen: usize) -> sds {
+    let mut ty = req_type(initlen);
+    // Empty strings are usually created in order to append. Use type 8
+    // since type 5 is not good at this.
+    if ty == TYPE_5 && initlen == 0 {
+        ty = TYPE_8;
+    }
+    let hdrlen = hdr_size(ty);
+    let Some(size) = hdrlen.checked_add(initlen).and_then(|n| n.checked_add(1)) else {
+        return ptr::null_mut();
+    };
+    let sh = malloc(size).cast::<c_char>();
+    if sh.is_null() {
+        return ptr::null_mut();
+    }
+    if init.is_null() {
+        ptr::write_bytes(sh, 0, size);
+    }
+    let s = sh.add(hdrlen);
+    match ty {
+        TYPE_5 => *s.sub(1) = (ty | ((initlen as u8) << SDS_TYPE_BITS)) as c_char,
+        TYPE_8 => {
+            let sh = hdr!(sdshdr8, s);
+            (*sh).len = initlen as u8;
+            (*sh).alloc = initlen as u8;
+            (*sh).flags = ty;
+        }
+        TYPE_16 => {
+            let sh = hdr!(sdshdr16, s);
+            (*sh).len = initlen as u16;
+            (*sh).alloc = initlen as u16;
+            (*sh).flags = ty;
+        }
+        TYPE_32 => {
+            let sh = hdr!(sdshdr32, s);
+            (*sh).len = initlen as u32;
+            (*sh).alloc = initlen as u32;
+            (*sh).flags = ty;
+        }
+        _ => {
+            let sh = hdr!(sdshdr64, s);
+            (*sh).len = initlen as u64;
+            (*sh).alloc = initlen as u64;
+            (*sh).flags = ty;
+        }
+    }
+    if initlen != 0 && !init.is_null() {
+        ptr::copy_nonoverlapping(init.cast::<c_char>(), s, initlen);
+    }
+    *s.add(initlen) = 0;
+    s
+}
+
+/// Create an empty (zero length) sds string. Even in this case the string
+/// always has an implicit null term.
+pub unsafe extern "C" fn sdsempty() -> sds {
+    sdsnewlen(c"".as_ptr().cast(), 0)
+}
+
+/// Create a new sds string starting from a null terminated C string.
+pub unsafe extern "C" fn sdsnew(init: *const c_char) -> sds {
+    let initlen = if init.is_null() {
+        0
+    } else {
+        CStr::from_ptr(init).count_bytes()
+    };
+    sdsnewlen(init.cast(), initlen)
+}
+
+/// Duplicate an sds string.
+pub unsafe extern "C" fn sdsdup(s: sds) -> sds {
+    sdsnewlen(s.cast(), sdslen(s))
+}
+
+/// Free an sds string. No operation is performed if 's' is NULL.
+pub unsafe extern "C" fn sdsfree(s: sds) {
+    if s.is_null() {
+        return;
+    }
+    free(s.sub(hdr_size(flags(s))).cast());
+}
+
+/// Set the sds string length to the length as obtained with strlen(), so
+/// considering as content only up to the first null term character.
+pub unsafe extern "C" fn sdsupdatelen(s: sds) {
+    sdssetlen(s, CStr::from_ptr(s).count_bytes());
+}
+
+/// Modify an sds string in-place to make it empty (zero length).
+/// However all the existing buffer is not discarded but set as free space
+/// so that next append operations will not require allocations up to the
+/// number of bytes previously available.
+pub unsafe extern "C" fn sdsclear(s: sds) {
+    sdssetlen(s, 0);
+    *s = 0;
+}
+
+/// Enlarge the free space at the end of the sds string so that the caller
+/// is sure that after calling this function can overwrite up to addlen
+/// bytes after the end of the string, plus one more byte for nul term.
+///
+/// Note: this does not change the *length* of the sds string as returned
+/// by sdslen(), but only the free buffer space we have.
+pub unsafe extern "C" fn sdsMakeRoomFor(s: sds, addlen: usize) -> sds {
+    let oldtype = flags(s) & TYPE_MASK;
+
+    // Return ASAP if there is enough space left.
+    if sdsavail(s) >= addlen {
+        return s;
+    }
+
+    let len = sdslen(s);
+    let sh = s.sub(hdr_size(oldtype));
+    let Some(mut newlen) = len.checked_add(addlen) else {
+        return ptr::null_mut();
+    };
+    if newlen < SDS_MAX_PREALLOC as usize {
+        newlen *= 2;
+    } else {
+        newlen = newlen.saturating_add(SDS_MAX_PREALLOC as usize);
+    }
+
+    let mut ty = req_type(newlen);
+
+    // Don't use type 5: the user is appending to the string and type 5 is
+    // not able to remember empty space, so sdsMakeRoomFor() must be called
+    // at every appending operation.
+    if ty == TYPE_5 {
+        ty = TYPE_8;
+    }
+
+    let hdrlen = hdr_size(ty);
+    let Some(size) = hdrlen.checked_add(newlen).and_then(|n| n.checked_add(1)) else {
+        return ptr::null_mut();
+    };
+    let s = if oldtype == ty {
+        let newsh = realloc(sh.cast(), size).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        newsh.add(hdrlen)
+    } else {
+        // Since the header size changes, need to move the string forward,
+        // and can't use realloc
+        let newsh = malloc(size).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        ptr::copy_nonoverlapping(s, newsh.add(hdrlen), len + 1);
+        free(sh.cast());
+        let s = newsh.add(hdrlen);
+        *s.sub(1) = ty as c_char;
+        sdssetlen(s, len);
+        s
+    };
+    sdssetalloc(s, newlen);
+    s
+}
+
+/// Reallocate the sds string so that it has no free space at the end. The
+/// contained string remains not altered, but next concatenation operations
+/// will require a reallocation.
+///
+/// After the call, the passed sds string is no longer valid and all the
+/// references must be substituted with the new pointer returned by the call.
+pub unsafe extern "C" fn sdsRemoveFreeSpace(s: sds) -> sds {
+    let oldtype = flags(s) & TYPE_MASK;
+    let oldhdrlen = hdr_size(oldtype);
+    let len = sdslen(s);
+    let sh = s.sub(oldhdrlen);
+
+    // Return ASAP if there is no space left.
+    if sdsavail(s) == 0 {
+        return s;
+    }
+
+    // Check what would be the minimum SDS header that is just good enough to
+    // fit this string.
+    let ty = req_type(len);
+    let hdrlen = hdr_size(ty);
+
+    // If the type is the same, or at least a large enough type is still
+    // required, we just realloc(), letting the allocator to do the copy
+    // only if really needed. Otherwise if the change is huge, we manually
+    // reallocate the string to use the different header type.
+    let s = if oldtype == ty || ty > TYPE_8 {
+        let newsh = realloc(sh.cast(), oldhdrlen + len + 1).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        newsh.add(oldhdrlen)
+    } else {
+        let newsh = malloc(hdrlen + len + 1).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        ptr::copy_nonoverlapping(s, newsh.add(hdrlen), len + 1);
+        free(sh.cast());
+        let s = newsh.add(hdrlen);
+        *s.sub(1) = ty as c_char;
+        sdssetlen(s, len);
+        s
+    };
+    sdssetalloc(s, len);
+    s
+}
+
+/// Resize the allocation, this can make the allocation bigger or smaller,
+/// if the size is smaller than currently used len, the data will be truncated
+pub unsafe extern "C" fn sdsResize(s: sds, size: usize) -> sds {
+    let oldtype = flags(s) & TYPE_MASK;
+    let oldhdrlen = hdr_size(oldtype);
+    let mut len = sdslen(s);
+    let sh = s.sub(oldhdrlen);
+
+    // Return ASAP if the size is already good.
+    if sdsalloc(s) == size {
+        return s;
+    }
+
+    // Truncate len if needed.
+    if size < len {
+        len = size;
+    }
+
+    // Check what would be the minimum SDS header that is just good enough to
+    // fit this string.
+    let mut ty = req_type(size);
+    // Don't use type 5, it is not good for strings that are resized.
+    if ty == TYPE_5 {
+        ty = TYPE_8;
+    }
+    let hdrlen = hdr_size(ty);
+
+    // If the type is the same, or can hold the size in it with low overhead
+    // (larger than SDS_TYPE_8), we just realloc(), letting the allocator
+    // to do the copy only if really needed. Otherwise if the change is
+    // huge, we manually reallocate the string to use the different header
+    // type.
+    let s = if oldtype == ty || (ty < oldtype && ty > TYPE_8) {
+        let Some(newsize) = oldhdrlen.checked_add(size).and_then(|n| n.checked_add(1)) else {
+            return ptr::null_mut();
+        };
+        let newsh = realloc(sh.cast(), newsize).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        newsh.add(oldhdrlen)
+    } else {
+        let Some(newsize) = hdrlen.checked_add(size).and_then(|n| n.checked_add(1)) else {
+            return ptr::null_mut();
+        };
+        let newsh = malloc(newsize).cast::<c_char>();
+        if newsh.is_null() {
+            return ptr::null_mut();
+        }
+        ptr::copy_nonoverlapping(s, newsh.add(hdrlen), len);
+        free(sh.cast());
+        let s = newsh.add(hdrlen);
+        *s.sub(1) = ty as c_char;
+        s
+    };
+    *s.add(len) = 0;
+    sdssetlen(s, len);
+    sdssetalloc(s, size);
+    s
+}
+
+/// Return the total size of the allocation of the specified sds string,
+/// including:
+/// 1) The sds header before the pointer.
+/// 2) The string.
+/// 3) The free buffer at the end if any.
+/// 4) The implicit null term.
+pub unsafe extern "C" fn sdsAllocSize(s: sds) -> usize {
+    hdr_size(flags(s)) + sdsalloc(s) + 1
+}
+
+/// Return the pointer of the actual SDS allocation (normally SDS strings
+/// are referenced by the start of the string buffer).
+pub unsafe extern "C" fn sdsAllocPtr(s: sds) -> *mut c_void {
+    s.sub(hdr_size(flags(s))).cast()
+}
+
+/// Increment the sds length and decrements the left free space at the
+/// end of the string according to 'incr'. Also set the null term
+/// in the new end of the string.
+///
+/// This function is used in order to fix the string length after the
+/// user calls sdsMakeRoomFor(), writes something after the end of
+/// the current string, and finally needs to set the new length.
+///
+/// Note: it is possible to use a negative increment in order to
+/// right-trim the string.
+pub unsafe extern "C" fn sdsIncrLen(s: sds, incr: ssize_t) {
+    let flags = flags(s);
+    let incr = incr as i128;
+    let len = match flags & TYPE_MASK {
+        TYPE_5 => {
+            let oldlen = (flags >> SDS_TYPE_BITS) as i128;
+            assert!((incr > 0 && oldlen + incr < 32) || (incr < 0 && oldlen >= -incr));
+            let len = (oldlen + incr) as usize;
+            *s.sub(1) = (TYPE_5 | ((len as u8) << SDS_TYPE_BITS)) as c_char;
+            len
+        }
+        TYPE_8 => {
+            let sh = hdr!(sdshdr8, s);
+            let (len, alloc) = ((*sh).len as i128, (*sh).alloc as i128);
+            assert!((incr >= 0 && alloc - len >= incr) || (incr < 0 && len >= -incr));
+            (*sh).len = (len + incr) as u8;
+            (len + incr) as usize
+        }
+        TYPE_16 => {
+            let sh = hdr!(sdshdr16, s);
+            let (len, alloc) = ((*sh).len as i128, (*sh).alloc as i128);
+            assert!((incr >= 0 && alloc - len >= incr) || (incr < 0 && len >= -incr));
+            (*sh).len = (len + incr) as u16;
+            (len + incr) as usize
+        }
+        TYPE_32 => {
+            let sh = hdr!(sdshdr32, s);
+            let (len, alloc) = ((*sh).len as i128, (*sh).alloc as i128);
+            assert!((incr >= 0 && alloc - len >= incr) || (incr < 0 && len >= -incr));
+            (*sh).len = (len + incr) as u32;
+            (len + incr) as usize
+        }
+        TYPE_64 => {
+            let sh = hdr!(sdshdr64, s);
+            let (len, alloc) = ((*sh).len as i128, (*sh).alloc as i128);
+            assert!((incr >= 0 && alloc - len >= incr) || (incr < 0 && len >= -incr));
+            (*sh).len = (len + incr) as u64;
+            (len + incr) as usize
+        }
+        _ => 0,
+    };
+    *s.add(len) = 0;
+}
+
+/// Grow the sds to have the specified length. Bytes that were not part of
+/// the original length of the sds will be set to zero.
+///
+/// if the specified length is smaller than the current length, no operation
+/// is performed.
+pub unsafe extern "C" fn sdsgrowzero(s: sds, len: usize) -> sds {
+    let curlen = sdslen(s);
+    if len <= curlen {
+        return s;
+    }
+    let s = sdsMakeRoomFor(s, len - curlen);
+    if s.is_null() {
+        return ptr::null_mut();
+    }
+
+    // Make sure added region doesn't contain garbage
+    ptr::write_bytes(s.add(curlen), 0, len - curlen + 1); // also set trailing \0 byte
+    sdssetlen(s, len);
+    s
+}
+
+/// Append the specified binary-safe string pointed by 't' of 'len' bytes to
+/// the end of the specified sds string 's'.
+///
+/// After the call, the passed sds string is no longer valid and all the
+/// references must be substituted with the new pointer returned by the call.
+pub unsafe extern "C" fn sdscatlen(s: sds, t: *const c_void, len: usize) -> sds {
+    let curlen = sdslen(s);
+
+    let s = sdsMakeRoomFor(s, len);
+    if s.is_null() {
+        return ptr::null_mut();
+    }
+    ptr::copy(t.cast::<c_char>(), s.add(curlen), len);
+    sdssetlen(s, curlen + len);
+    *s.add(curlen + len) = 0;
+    s
+}
+
+/// Append the specified null terminated C string to the sds string 's'.
+pub unsafe extern "C" fn sdscat(s: sds, t: *const c_char) -> sds {
+    sdscatlen(s, t.cast(), CStr::from_ptr(t).count_bytes())
+}
+
+/// Append the specified sds 't' to the existing sds 's'.
+pub unsafe extern "C" fn sdscatsds(s: sds, t: sds) -> sds {
+    sdscatlen(s, t.cast(), sdslen(t))
+}
+
+/// Destructively modify the sds string 's' to hold the specified binary
+/// safe string pointed by 't' of length 'len' bytes.
+pub unsafe extern "C" fn sdscpylen(s: sds, t: *const c_char, len: usize) -> sds {
+    let mut s = s;
+    if sdsalloc(s) < len {
+        s = sdsMakeRoomFor(s, len - sdslen(s));
+        if s.is_null() {
+            return ptr::null_mut();
+        }
+    }
+    ptr::copy(t, s, len);
+    *s.add(len) = 0;
+    sdssetlen(s, len);
+    s
+}
+
+/// Like sdscpylen() but 't' must be a null-terminated string so that the
+/// length of the string is obtained with strlen().
+pub unsafe extern "C" fn sdscpy(s: sds, t: *const c_char) -> sds {
+    sdscpylen(s, t, CStr::from_ptr(t).count_bytes())
+}
+
+/// Create an sds string from a long long value. It is much faster than:
+///
+/// sdscatprintf(sdsempty(),"%lld\n", value);
+pub unsafe extern "C" fn sdsfromlonglong(value: c_longlong) -> sds {
+    let mut buf = [0u8; 21];
+    let mut v = value.unsigned_abs();
+    let mut p = buf.len();
+    loop {
+        p -= 1;
+        buf[p] = b'0' + (v % 10) as u8;
+        v /= 10;
+        if v == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        p -= 1;
+        buf[p] = b'-';
+    }
+    sdsnewlen(buf[p..].as_ptr().cast(), buf.len() - p)
+}
+
+/// Remove the part of the string from left and from right composed just of
+/// contiguous characters found in 'cset', that is a null terminated C string.
+///
+/// Like `strchr()` in the C version, the nul byte always counts as part of
+/// 'cset'.
+pub unsafe extern "C" fn sdstrim(s: sds, cset: *const c_char) -> sds {
+    let cset = CStr::from_ptr(cset).to_bytes_with_nul();
+    let bytes = std::slice::from_raw_parts(s as *const u8, sdslen(s));
+    let start = bytes
+        .iter()
+        .position(|c| !cset.contains(c))
+        .unwrap_or(bytes.len());
+    let end = bytes[start..]
+        .iter()
+        .rposition(|c| !cset.contains(c))
+        .map_or(start, |end| start + end + 1);
+    let len = end - start;
+    if start != 0 {
+        ptr::copy(s.add(start), s, len);
+    }
+    *s.add(len) = 0;
+    sdssetlen(s, len);
+    s
+}
+
+/// Changes the input string to be a subset of the original.
+/// It does not release the free space in the string, so a call to
+/// sdsRemoveFreeSpace may be wise after.
+pub unsafe extern "C" fn sdssubstr(s: sds, start: usize, len: usize) {
+    // Clamp out of range input
+    let oldlen = sdslen(s);
+    let (start, mut len) = if start >= oldlen {
+        (0, 0)
+    } else {
+        (start, len)
+    };
+    if len > oldlen - start {
+        len = oldlen - start;
+    }
+
+    // Move the data
+    if len != 0 {
+        ptr::copy(s.add(start), s, len);
+    }
+    *s.add(len) = 0;
+    sdssetlen(s, len);
+}
+
+/// Turn the string into a smaller (or equal) string containing only the
+/// substring specified by the 'start' and 'end' indexes.
+///
+/// start and end can be negative, where -1 means the last character of the
+/// string, -2 the penultimate character, and so forth.
+///
+/// The interval is inclusive, so the start and end characters will be part
+/// of the resulting string.
+pub unsafe extern "C" fn sdsrange(s: sds, start: ssize_t, end: ssize_t) {
+    let len = sdslen(s) as ssize_t;
+    if len == 0 {
+        return;
+    }
+    let mut start = start;
+    let mut end = end;
+    if start < 0 {
+        start = (len + start).max(0);
+    }
+    if end < 0 {
+        end = (len + end).max(0);
+    }
+    // Capped before the subtraction, which sds.c lets overflow.
+    if end >= len {
+        end = len - 1;
+    }
+    let newlen = if start > end { 0 } else { (end - start) + 1 };
+    sdssubstr(s, start as usize, newlen as usize);
+}
+
+/// Apply tolower() to every character of the sds string 's'.
+pub unsafe extern "C" fn sdstolower(s: sds) {
+    std::slice::from_raw_parts_mut(s as *mut u8, sdslen(s)).make_ascii_lowercase();
+}
+
+/// Apply toupper() to every character of the sds string 's'.
+pub unsafe extern "C" fn sdstoupper(s: sds) {
+    std::slice::from_raw_parts_mut(s as *mut u8, sdslen(s)).make_ascii_uppercase();
+}
+
+/// Compare two sds strings s1 and s2 with memcmp().
+///
+/// Return value:
+///
+/// positive if s1 > s2.
+/// negative if s1 < s2.
+/// 0 if s1 and s2 are exactly the same binary string.
+///
+/// If two strings share exactly the same prefix, but one of the two has
+/// additional characters, the longer string is considered to be greater than
+/// the smaller one.
+pub unsafe extern "C" fn sdscmp(s1: sds, s2: sds) -> c_int {
+    let s1 = std::slice::from_raw_parts(s1 as *const u8, sdslen(s1));
+    let s2 = std::slice::from_raw_parts(s2 as *const u8, sdslen(s2));
+    s1.cmp(s2) as c_int
+}
+
+/// Split 's' with separator in 'sep'. An array of sds strings is returned.
+/// *count will be set by reference to the number of tokens returned.
+///
+/// On out of memory, zero length string, zero length separator, NULL is
+/// returned.
+///
+/// Note that 'sep' is able to split a string using a multi-character
+/// separator. For example sdssplit("foo_-_bar","_-_"); will return two
+/// elements "foo" and "bar".
+pub unsafe extern "C" fn sdssplitlen(
+    s: *const c_char,
+    len: ssize_t,
+    sep: *const c_char,
+    seplen: c_int,
+    count: *mut c_int,
+) -> *mut sds {
+    if seplen < 1 || len <= 0 {
+        *count = 0;
+        return ptr::null_mut();
+    }
+    let (len, seplen) = (len as usize, seplen as usize);
+    let mut slots = 5;
+    let mut elements = 0;
+    let mut tokens = malloc(std::mem::size_of::<sds>() * slots).cast::<sds>();
+    if tokens.is_null() {
+        return ptr::null_mut();
+    }
+
+    let bytes = std::slice::from_raw_parts(s as *const u8, len);
+    let sep = std::slice::from_raw_parts(sep as *const u8, seplen);
+    let mut start = 0;
+    let mut j = 0;
+    let cleanup = |tokens: *mut sds, elements: usize| {
+        for i in 0..elements {
+            sdsfree(*tokens.add(i));
+        }
+        free(tokens.cast());
+        *count = 0;
+        ptr::null_mut()
+    };
+    while j + seplen <= len {
+        // make sure there is room for the next element and the final one
+        if slots < elements + 2 {
+            slots *= 2;
+            let newtokens = realloc(tokens.cast(), std::mem::size_of::<sds>() * slots);
+            if newtokens.is_null() {
+                return cleanup(tokens, elements);
+            }
+            tokens = newtokens.cast();
+        }
+        // search the separator
+        if bytes[j..j + seplen] == *sep {
+            let token = sdsnewlen(s.add(start).cast(), j - start);
+            if token.is_null() {
+                return cleanup(tokens, elements);
+            }
+            *tokens.add(elements) = token;
+            elements += 1;
+            start = j + seplen;
+            j += seplen; // skip the separator
+        } else {
+            j += 1;
+        }
+    }
+    // Add the final element. We are sure there is room in the tokens array.
+    let token = sdsnewlen(s.add(start).cast(), len - start);
+    if token.is_null() {
+        return cleanup(tokens, elements);
+    }
+    *tokens.add(elements) = token;
+    elements += 1;
+    *count = elements as c_int;
+    tokens
+}
+
+/// Free the result returned by sdssplitlen(), or do nothing if 'tokens' is
+/// NULL.
+pub unsafe extern "C" fn sdsfreesplitres(tokens: *mut sds, count: c_int) {
+    if tokens.is_null() {
+        return;
+    }
+    for i in (0..count.max(0) as usize).rev() {
+        sdsfree(*tokens.add(i));
+    }
+    free(tokens.cast());
+}
+
+/// Append to the sds string "s" an escaped string representation where
+/// all the non-printable characters (tested with isprint()) are turned into
+/// escapes in the form "\n\r\a...." or "\x<hex-number>".
+pub unsafe extern "C" fn sdscatrepr(s: sds, p: *const c_char, len: usize) -> sds {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let bytes = std::slice::from_raw_parts(p as *const u8, len);
+    let mut s = sdscatlen(s, c"\"".as_ptr().cast(), 1);
+    for &c in bytes {
+        let escaped: &[u8] = match c {
+            b'\\' => b"\\\\",
+            b'"' => b"\\\"",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            0x07 => b"\\a",
+            0x08 => b"\\b",
+            0x20..=0x7e => &[c],
+            _ => &[b'\\', b'x', HEX[(c >> 4) as usize], HEX[(c & 0xf) as usize]],
+        };
+        s = sdscatlen(s, escaped.as_ptr().cast(), escaped.len());
+    }
+    sdscatlen(s, c"\"".as_ptr().cast(), 1)
+}
+
+/// Returns one if the string contains characters to be escaped
+/// by sdscatrepr(), zero otherwise.
+///
+/// Typically, this should be used to help protect aggregated strings in a way
+/// that is compatible with sdssplitargs(). For this reason, also spaces will be
+/// treated as needing an escape.
+pub unsafe extern "C" fn sdsneedsrepr(s: sds) -> c_int {
+    std::slice::from_raw_parts(s as *const u8, sdslen(s))
+        .iter()
+        .any(|&c| {
+            matches!(c, b'\\' | b'"' | b'\n' | b'\r' | b'\t' | 0x07 | 0x08)
+                || !(0x20..=0x7e).contains(&c)
+                || is_space(c)
+        }) as c_int
+}
+
+/// `isspace()` in the C locale.
+fn is_space(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | 0x0b | 0x0c | b'\r')
+}
+
+fn hex_digit_to_int(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => 0,
+    }
+}
+
+/// Split a line into arguments, where every argument can be in the
+/// following programming-language REPL-alike form:
+///
+/// foo bar "newline are supported\n" and "\xff\x00otherstuff"
+///
+/// The number of arguments is stored into *argc, and an array
+/// of sds is returned.
+///
+/// The caller should free the resulting array of sds strings with
+/// sdsfreesplitres().
+///
+/// The function returns the allocated tokens on success, even when the
+/// input string is empty, or NULL if the input contains unbalanced
+/// quotes or closed quotes followed by non space characters
+/// as in: "foo"bar or "foo'
+pub unsafe extern "C" fn sdssplitargs(line: *const c_char, argc: *mut c_int) -> *mut sds {
+    // The C version walks the string up to its nul terminator and peeks one
+    // or two bytes ahead, which is always in bounds because it stops at the
+    // terminator first.
+    let line = CStr::from_ptr(line).to_bytes_with_nul();
+    let at = |i: usize| line.get(i).copied().unwrap_or(0);
+    let mut p = 0;
+    let mut vector: *mut sds = ptr::null_mut();
+    let mut current: sds = ptr::null_mut();
+
+    *argc = 0;
+    unsafe fn err(vector: *mut sds, current: sds, argc: *mut c_int) -> *mut sds {
+        while *argc > 0 {
+            *argc -= 1;
+            sdsfree(*vector.add(*argc as usize));
+        }
+        free(vector.cast());
+        if !current.is_null() {
+            sdsfree(current);
+        }
+        *argc = 0;
+        ptr::null_mut()
+    }
+    loop {
+        // skip blanks
+        while at(p) != 0 && is_space(at(p)) {
+            p += 1;
+        }
+        if at(p) == 0 {
+            // Even on empty input string return something not NULL.
+            if vector.is_null() {
+                vector = malloc(std::mem::size_of::<*mut c_void>()).cast();
+            }
+            return vector;
+        }
+
+        // get a token
+        let mut inq = false; // set to true if we are in "quotes"
+        let mut insq = false; // set to true if we are in 'single quotes'
+        let mut done = false;
+
+        if current.is_null() {
+            current = sdsempty();
+        }
+        while !done {
+            let c = at(p);
+            if inq {
+                if c == b'\\'
+                    && at(p + 1) == b'x'
+                    && at(p + 2).is_ascii_hexdigit()
+                    && at(p + 3).is_ascii_hexdigit()
+                {
+                    let byte = hex_digit_to_int(at(p + 2)) * 16 + hex_digit_to_int(at(p + 3));
+                    current = sdscatlen(current, ptr::addr_of!(byte).cast(), 1);
+                    p += 3;
+                } else if c == b'\\' && at(p + 1) != 0 {
+                    p += 1;
+                    let byte = match at(p) {
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        b'b' => 0x08,
+                        b'a' => 0x07,
+                        other => other,
+                    };
+                    current = sdscatlen(current, ptr::addr_of!(byte).cast(), 1);
+                } else if c == b'"' {
+                    // closing quote must be followed by a space or
+                    // nothing at all.
+                    if at(p + 1) != 0 && !is_space(at(p + 1)) {
+                        return err(vector, current, argc);
+                    }
+                    done = true;
+                } else if c == 0 {
+                    // unterminated quotes
+                    return err(vector, current, argc);
+                } else {
+                    current = sdscatlen(current, line.as_ptr().add(p).cast(), 1);
+                }
+            } else if insq {
+                if c == b'\\' && at(p + 1) == b'\'' {
+                    p += 1;
+                    current = sdscatlen(current, c"'".as_ptr().cast(), 1);
+                } else if c == b'\'' {
+                    // closing quote must be followed by a space or
+                    // nothing at all.
+                    if at(p + 1) != 0 && !is_space(at(p + 1)) {
+                        return err(vector, current, argc);
+                    }
+                    done = true;
+                } else if c == 0 {
+                    // unterminated quotes
+                    return err(vector, current, argc);
+                } else {
+                    current = sdscatlen(current, line.as_ptr().add(p).cast(), 1);
+                }
+            } else {
+                match c {
+                    b' ' | b'\n' | b'\r' | b'\t' | 0 => done = true,
+                    b'"' => inq = true,
+                    b'\'' => insq = true,
+                    _ => current = sdscatlen(current, line.as_ptr().add(p).cast(), 1),
+                }
+            }
+            if at(p) != 0 {
+                p += 1;
+            }
+        }
+        // add the token to the vector
+        vector = realloc(
+            vector.cast(),
+            (*argc as usize + 1) * std::mem::size_of::<*mut c_char>(),
+        )
+        .cast();
+        *vector.add(*argc as usize) = current;
+        *argc += 1;
+        current = ptr::null_mut();
+    }
+}
+
+/// Modify the string substituting all the occurrences of the set of
+/// characters specified in the 'from' string to the corresponding character
+/// in the 'to' array.
+///
+/// For instance: sdsmapchars(mystring, "ho", "01", 2)
+/// will have the effect of turning the string "hello" into "0ell1".
+///
+/// The function returns the sds string pointer, that is always the same
+/// as the input pointer since no resize is needed.
+pub unsafe extern "C" fn sdsmapchars(
+    s: sds,
+    from: *const c_char,
+    to: *const c_char,
+    setlen: usize,
+) -> sds {
+    let from = std::slice::from_raw_parts(from, setlen);
+    let to = std::slice::from_raw_parts(to, setlen);
+    for c in std::slice::from_raw_parts_mut(s, sdslen(s)) {
+        if let Some(i) = from.iter().position(|f| f == c) {
+            *c = to[i];
+        }
+    }
+    s
+}
+
+/// Join an array of C strings using the specified separator (also a C string).
+/// Returns the result as an sds string.
+pub unsafe extern "C" fn sdsjoin(argv: *mut *mut c_char, argc: c_int, sep: *mut c_char) -> sds {
+    let mut join = sdsempty();
+    for j in 0..argc {
+        join = sdscat(join, *argv.add(j as usize));
+        if j != argc - 1 {
+            join = sdscat(join, sep);
+        }
+    }
+    join
+}
+
+/// Like sdsjoin, but joins an array of SDS strings.
+pub unsafe extern "C" fn sdsjoinsds(
+    argv: *mut sds,
+    argc: c_int,
+    sep: *const c_char,
+    seplen: usize,
+) -> sds {
+    let mut join = sdsempty();
+    for j in 0..argc {
+        join = sdscatsds(join, *argv.add(j as usize));
+        if j != argc - 1 {
+            join = sdscatlen(join, sep.cast(), seplen);
+        }
+    }
+    join
+}
+
+/// Wrappers to the allocators used by SDS. Note that SDS will actually
+/// just use the macros defined into sdsalloc.h in order to avoid to pay
+/// the overhead of function calls. Here we define these wrappers only for
+/// the programs SDS is linked to, if they want to touch the SDS internals
+/// even if they use a different allocator.
+pub unsafe extern "C" fn sds_malloc(size: usize) -> *mut c_void {
+    malloc(size)
+}
+
+pub unsafe extern "C" fn sds_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    realloc(ptr, size)
+}
+
+pub unsafe extern "C" fn sds_free(ptr: *mut c_void) {
+    free(ptr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn bytes<'a>(s: sds) -> &'a [u8] {
+        std::slice::from_raw_parts(s as *const u8, sdslen(s))
+    }
+
+    unsafe fn range(init: &str, start: ssize_t, end: ssize_t) -> Vec<u8> {
+        let s = sdsnewlen(init.as_ptr().cast(), init.len());
+        sdsrange(s, start, end);
+        assert_eq!(*s.add(sdslen(s)), 0);
+        let range = bytes(s).to_vec();
+        sdsfree(s);
+        range
+    }
+
+    #[test]
+    fn test_sdsrange() {
+        unsafe {
+            assert_eq!(range("Hello World", 1, -1), b"ello World");
+            assert_eq!(range("Hello World", -5, 100), b"World");
+            assert_eq!(range("Hello World", 2, 1), b"");
+            assert_eq!(range("Hello World", 11, 20), b"");
+            assert_eq!(range("Hello World", -100, -100), b"H");
+            assert_eq!(range("Hello World", 0, ssize_t::MAX), b"Hello World");
+            assert_eq!(
+                range("Hello World", ssize_t::MIN, ssize_t::MAX),
+                b"Hello World"
+            );
+            assert_eq!(range("Hello World", ssize_t::MAX, ssize_t::MIN), b"");
+            assert_eq!(range("", 0, ssize_t::MAX), b"");
+        }
+    }
+
+    #[test]
+    fn test_sdsMakeRoomFor() {
+        unsafe {
+            let s = sdsnew(c"abc".as_ptr());
+            assert_eq!(flags(s) & TYPE_MASK, TYPE_5);
+            let s = sdsMakeRoomFor(s, 1);
+            // Type 5 can't record free space, so growing always leaves it.
+            assert_eq!(flags(s) & TYPE_MASK, TYPE_8);
+            assert!(sdsavail(s) >= 1);
+            assert_eq!(bytes(s), b"abc");
+
+            let s = sdsMakeRoomFor(s, 300);
+            assert_eq!(flags(s) & TYPE_MASK, TYPE_16);
+            assert!(sdsavail(s) >= 300);
+            assert_eq!(bytes(s), b"abc");
+
+            assert!(sdsMakeRoomFor(s, usize::MAX).is_null());
+            assert!(sdsMakeRoomFor(s, usize::MAX - 3).is_null());
+            assert_eq!(bytes(s), b"abc");
+            sdsfree(s);
+        }
+    }
+
+    unsafe fn split(s: &[u8], sep: &[u8]) -> Option<Vec<Vec<u8>>> {
+        let mut count = -1;
+        let tokens = sdssplitlen(
+            s.as_ptr().cast(),
+            s.len() as ssize_t,
+            sep.as_ptr().cast(),
+            sep.len() as c_int,
+            &mut count,
+        );
+        if tokens.is_null() {
+            assert_eq!(count, 0);
+            return None;
+        }
+        let split = (0..count as usize)
+            .map(|i| bytes(*tokens.add(i)).to_vec())
+            .collect();
+        sdsfreesplitres(tokens, count);
+        Some(split)
+    }
+
+    #[test]
+    fn test_sdssplitlen() {
+        unsafe {
+            assert_eq!(split(b"", b","), None);
+            assert_eq!(split(b"a,b", b""), None);
+            assert_eq!(split(b",", b","), Some(vec![vec![]; 2]));
+            assert_eq!(
+                split(b"foo_-_bar_-", b"_-_"),
+                Some(vec![b"foo".to_vec(), b"bar_-".to_vec()])
+            );
+            assert_eq!(split(b"a,b,c,d,e,f", b",").unwrap().len(), 6);
+            assert_eq!(split(b"ab", b"abc"), Some(vec![b"ab".to_vec()]));
+        }
+    }
+}
@@ -1,29 +1,172 @@
 use std::env;
 use std::path::PathBuf;
 
+/// The features of this crate that choose or change the library.
+const FEATURES: [&str; 7] = [
+    "system",
+    "vendored",
+    "rust-alloc",
+    "sanitize",
+    "antirez",
+    "redis7",
+    "pure-rust",
+];
+
+/// The enabled entries of [`FEATURES`].
+fn enabled_features() -> Vec<&'static str> {
+    FEATURES
+        .into_iter()
+        .filter(|feature| {
+            let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+            env::var_os(var).is_some()
+        })
+        .collect()
+}
+
+/// Where the SDS library that we end up linking lives.
+struct Library {
+    /// Directories that contain `sds.h` (and `sdsalloc.h`).
+    include_paths: Vec<PathBuf>,
+    /// Directories that contain `libsds` itself.
+    lib_paths: Vec<PathBuf>,
+}
+
 fn main() {
-    cc::Build::new()
-        .file("sds/sds.c")
-        .include("sds")
-        .compile("sds");
+    println!("cargo:rerun-if-env-changed=SDS_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=SDS_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=SDS_STATIC");
+    println!("cargo:rerun-if-env-changed=SDS_SANITIZE");
+
+    println!("cargo:rustc-check-cfg=cfg(sds_vendored)");
+    println!(
+        "cargo:rustc-check-cfg=cfg(sds_flavor, values(\"jcorporation\", \"antirez\", \"redis7\"))"
+    );
+
+    let flavor = Flavor::from_features();
+    // `DEP_SDS_FLAVOR` for dependents, which gate their API on it.
+    println!("cargo:flavor={}", flavor.name());
+    println!("cargo:rustc-cfg=sds_flavor=\"{}\"", flavor.name());
+    // `DEP_SDS_FEATURES`, for dependents that run a nested cargo which has to
+    // pick the same library.
+    println!("cargo:features={}", enabled_features().join(","));
+
+    // Nothing to compile or link, everything comes from sds-pure.
+    if cfg!(feature = "pure-rust") {
+        if flavor != Flavor::Jcorporation {
+            panic!("The `pure-rust` feature only implements the jcorporation flavor of sds.");
+        }
+        return;
+    }
+
+    // A prebuilt libsds is hardwired to libc malloc and built without
+    // sanitizers, only the vendored copy can be changed.
+    let library = if cfg!(feature = "rust-alloc") {
+        build_vendored(
+            flavor,
+            "the `rust-alloc` feature needs the vendored build".to_string(),
+        )
+    } else if sanitizers().is_some() {
+        build_vendored(flavor, "sanitizers need the vendored build".to_string())
+    } else {
+        match find_system() {
+            Ok(library) => library,
+            Err(error) => build_vendored(flavor, error),
+        }
+    };
 
-    // Tell cargo to look for shared libraries in the specified directory
+    // Exposed to the build scripts of crates that depend on us as
+    // `DEP_SDS_INCLUDE` and `DEP_SDS_LIB`. Both may hold several paths, use
+    // `env::split_paths` to read them.
     println!(
-        "cargo:rustc-link-search={}",
-        std::env::var("OUT_DIR").unwrap()
+        "cargo:include={}",
+        env::join_paths(&library.include_paths)
+            .unwrap()
+            .to_string_lossy()
     );
+    println!(
+        "cargo:lib={}",
+        env::join_paths(&library.lib_paths)
+            .unwrap()
+            .to_string_lossy()
+    );
+
+    #[cfg(feature = "bindgen")]
+    generate_bindings(flavor, &library);
+}
+
+/// The upstream variant of sds.c, picked with the `antirez` and `redis7`
+/// features.
+#[derive(Clone, Copy, PartialEq)]
+enum Flavor {
+    /// jcorporation/sds, the default.
+    Jcorporation,
+    /// antirez/sds 2.0.
+    Antirez,
+    /// `src/sds.c` from Redis 7.2.
+    Redis7,
+}
 
-    // Tell cargo to tell rustc to link the system bzip2
-    // shared library.
-    println!("cargo:rustc-link-lib=sds");
+impl Flavor {
+    fn from_features() -> Flavor {
+        match (cfg!(feature = "antirez"), cfg!(feature = "redis7")) {
+            (false, false) => Flavor::Jcorporation,
+            (true, false) => Flavor::Antirez,
+            (false, true) => Flavor::Redis7,
+            (true, true) => panic!("The `antirez` and `redis7` features are mutually exclusive."),
+        }
+    }
 
+    fn name(self) -> &'static str {
+        match self {
+            Flavor::Jcorporation => "jcorporation",
+            Flavor::Antirez => "antirez",
+            Flavor::Redis7 => "redis7",
+        }
+    }
+
+    /// The git submodule with the sources, and where `sds.c` and `sds.h` are
+    /// inside of it.
+    #[cfg(feature = "vendored")]
+    fn sources(self) -> (&'static str, &'static str) {
+        match self {
+            Flavor::Jcorporation => ("sds", "sds"),
+            Flavor::Antirez => ("sds-antirez", "sds-antirez"),
+            Flavor::Redis7 => ("redis", "redis/src"),
+        }
+    }
+}
+
+/// Regenerates the bindings for the `sds.h` we are linking against.
+///
+/// Without the `bindgen` feature the checked-in `src/bindings.rs` (or
+/// `src/bindings_<flavor>.rs`) is used instead so that building doesn't
+/// require libclang.
+#[cfg(feature = "bindgen")]
+fn generate_bindings(flavor: Flavor, library: &Library) {
     // The bindgen::Builder is the main entry point
     // to bindgen, and lets you build up options for
     // the resulting bindings.
     let bindings = bindgen::Builder::default()
         // The input header we would like to generate
         // bindings for.
-        .header("sds/sds.h")
+        .header(find_header(flavor, library).to_string_lossy())
+        .clang_args(
+            library
+                .include_paths
+                .iter()
+                .map(|path| format!("-I{}", path.display())),
+        )
+        // Only keep the SDS API, not everything that sds.h pulls in from libc.
+        .allowlist_function("sds.*")
+        .allowlist_type("sds.*")
+        .allowlist_var("SDS_.*")
+        // `va_list` and the `ssize_t` typedef come out differently on every
+        // target, which the checked-in bindings can't follow. `ssize_t` is
+        // pointer sized everywhere libsds builds, and `sdscatvprintf` can't be
+        // called from Rust without a `va_list` anyway.
+        .blocklist_function("sdscatvprintf")
+        .blocklist_type("ssize_t")
+        .raw_line("pub type ssize_t = isize;")
         // Tell cargo to invalidate the built crate whenever any of the
         // included header files changed.
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
@@ -38,3 +181,204 @@ fn main() {
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Couldn't write bindings!");
 }
+
+/// Tries to find a libsds that is already installed on the system.
+///
+/// `SDS_LIB_DIR` (plus the optional `SDS_INCLUDE_DIR`) takes priority over
+/// pkg-config. Set `SDS_STATIC=1` to link the static `libsds.a` or `SDS_STATIC=0`
+/// to force the shared library.
+#[cfg(feature = "system")]
+fn find_system() -> Result<Library, String> {
+    let statik = env::var("SDS_STATIC").ok().map(|v| v != "0");
+
+    if let Some(lib_dir) = env::var_os("SDS_LIB_DIR").map(PathBuf::from) {
+        let include_dir = env::var_os("SDS_INCLUDE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| lib_dir.join("../include"));
+        // Without an explicit choice prefer the shared library, just like the
+        // linker would, unless only the archive is there.
+        let statik = statik
+            .unwrap_or_else(|| !has_shared_library(&lib_dir) && lib_dir.join("libsds.a").exists());
+
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+        if statik {
+            println!("cargo:rustc-link-lib=static=sds");
+        } else {
+            println!("cargo:rustc-link-lib=dylib=sds");
+        }
+        return Ok(Library {
+            include_paths: vec![include_dir],
+            lib_paths: vec![lib_dir],
+        });
+    }
+
+    pkg_config::Config::new()
+        .statik(statik.unwrap_or(false))
+        .probe("sds")
+        .map(|library| Library {
+            include_paths: library.include_paths,
+            lib_paths: library.link_paths,
+        })
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(not(feature = "system"))]
+fn find_system() -> Result<Library, String> {
+    Err("the `system` feature is disabled".to_string())
+}
+
+#[cfg(feature = "system")]
+fn has_shared_library(lib_dir: &std::path::Path) -> bool {
+    ["libsds.so", "libsds.dylib", "sds.lib", "sds.dll.lib"]
+        .iter()
+        .any(|name| lib_dir.join(name).exists())
+}
+
+/// Compiles `sds.c` of `flavor` from its git submodule into a static library.
+///
+/// The result is laid out like an installation prefix in `$OUT_DIR` (exposed
+/// as `DEP_SDS_ROOT`) with the headers in `include/` and `libsds.a` in `lib/`.
+#[cfg(feature = "vendored")]
+fn build_vendored(flavor: Flavor, _system_error: String) -> Library {
+    let (submodule, dir) = flavor.sources();
+    let dir = PathBuf::from(dir);
+    if !dir.join("sds.c").exists() {
+        panic!(
+            "{} is missing, run `git submodule update --init crates/sds-sys/{submodule}`",
+            dir.join("sds.c").display()
+        );
+    }
+
+    let root = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let include_dir = root.join("include");
+    let src_dir = root.join("src");
+    let lib_dir = root.join("lib");
+    std::fs::create_dir_all(&include_dir).unwrap();
+    std::fs::create_dir_all(&src_dir).unwrap();
+
+    // sds.c is compiled from a copy so that `#include "sdsalloc.h"` resolves
+    // to the header in `include/` rather than the one next to the original.
+    // Redis's own sdsalloc.h pulls in zmalloc, so that flavor always uses
+    // ours, which also handles `rust-alloc`.
+    let mut files = vec![
+        (dir.join("sds.c"), src_dir.join("sds.c")),
+        (dir.join("sds.h"), include_dir.join("sds.h")),
+    ];
+    match flavor {
+        Flavor::Redis7 => {
+            files.push((
+                PathBuf::from("src/redis7/sdsalloc.h"),
+                include_dir.join("sdsalloc.h"),
+            ));
+            files.push((
+                PathBuf::from("src/redis7/redisassert.h"),
+                include_dir.join("redisassert.h"),
+            ));
+        }
+        _ if cfg!(feature = "rust-alloc") => {
+            files.push((
+                PathBuf::from("src/sdsalloc.h"),
+                include_dir.join("sdsalloc.h"),
+            ));
+        }
+        _ => files.push((dir.join("sdsalloc.h"), include_dir.join("sdsalloc.h"))),
+    }
+    for (from, to) in files {
+        println!("cargo:rerun-if-changed={}", from.display());
+        std::fs::copy(&from, to)
+            .unwrap_or_else(|error| panic!("Couldn't copy {}: {error}", from.display()));
+    }
+
+    let mut build = cc::Build::new();
+    build
+        .file(src_dir.join("sds.c"))
+        .include(&include_dir)
+        .out_dir(&lib_dir);
+    if cfg!(feature = "rust-alloc") {
+        build.define("SDS_RUST_ALLOC", None);
+    }
+    if let Some(sanitizers) = sanitizers() {
+        build
+            .flag(format!("-fsanitize={sanitizers}"))
+            .flag("-fno-sanitize-recover=all")
+            .flag("-fno-omit-frame-pointer")
+            .debug(true);
+        link_sanitizer_runtimes(&build, &sanitizers);
+    }
+    // Also emits the `rustc-link-search` and `rustc-link-lib=static=sds`
+    // directives for us.
+    build.compile("sds");
+    println!("cargo:rustc-cfg=sds_vendored");
+    println!("cargo:root={}", root.display());
+
+    Library {
+        include_paths: vec![include_dir],
+        lib_paths: vec![lib_dir],
+    }
+}
+
+/// Links the runtimes of the sanitizers that sds.c was compiled with.
+///
+/// When the Rust code itself is built with `-Zsanitizer` (nightly only) rustc
+/// links them already. Otherwise we link GCC's shared runtimes, which is
+/// enough to sanitize sds.c from stable Rust.
+#[cfg(feature = "vendored")]
+fn link_sanitizer_runtimes(build: &cc::Build, sanitizers: &str) {
+    if env::var_os("CARGO_CFG_SANITIZE").is_some() {
+        return;
+    }
+    let compiler = build.get_compiler();
+    if !compiler.is_like_gnu() || compiler.is_like_clang() {
+        println!(
+            "cargo:warning=Only GCC's sanitizer runtimes can be linked on stable, build with \
+             RUSTFLAGS=-Zsanitizer=... on nightly instead."
+        );
+        return;
+    }
+    for sanitizer in sanitizers.split(',') {
+        match sanitizer.trim() {
+            "address" => println!("cargo:rustc-link-lib=dylib=asan"),
+            "undefined" => println!("cargo:rustc-link-lib=dylib=ubsan"),
+            "leak" => println!("cargo:rustc-link-lib=dylib=lsan"),
+            "thread" => println!("cargo:rustc-link-lib=dylib=tsan"),
+            other => println!("cargo:warning=Don't know the runtime of the `{other}` sanitizer"),
+        }
+    }
+}
+
+#[cfg(not(feature = "vendored"))]
+fn build_vendored(_flavor: Flavor, system_error: String) -> Library {
+    panic!(
+        "Could not find a system libsds ({system_error}) and the `vendored` feature is disabled. \
+         Install libsds with pkg-config support, point SDS_LIB_DIR at it, or enable the \
+         `vendored` feature."
+    );
+}
+
+/// The sanitizers to build the vendored sds.c with, as a comma separated list
+/// for `-fsanitize=`.
+///
+/// Taken from `SDS_SANITIZE` (`SDS_SANITIZE=0` turns them off), defaulting to
+/// `address,undefined` with the `sanitize` feature.
+fn sanitizers() -> Option<String> {
+    match env::var("SDS_SANITIZE") {
+        Ok(sanitizers) if sanitizers.is_empty() || sanitizers == "0" => None,
+        Ok(sanitizers) => Some(sanitizers),
+        Err(_) => cfg!(feature = "sanitize").then(|| "address,undefined".to_string()),
+    }
+}
+
+/// Picks the `sds.h` that belongs to the library we are linking.
+#[cfg(feature = "bindgen")]
+fn find_header(flavor: Flavor, library: &Library) -> PathBuf {
+    library
+        .include_paths
+        .iter()
+        .map(|path| path.join("sds.h"))
+        .find(|header| header.exists())
+        .unwrap_or_else(|| match flavor {
+            Flavor::Jcorporation => PathBuf::from("sds/sds.h"),
+            Flavor::Antirez => PathBuf::from("sds-antirez/sds.h"),
+            Flavor::Redis7 => PathBuf::from("redis/src/sds.h"),
+        })
+}
@@ -0,0 +1,253 @@
+/* automatically generated by rust-bindgen 0.70.1 */
+
+pub type ssize_t = isize;
+
+#[repr(C)]
+#[derive(Default)]
+pub struct __IncompleteArrayField<T>(::std::marker::PhantomData<T>, [T; 0]);
+impl<T> __IncompleteArrayField<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        __IncompleteArrayField(::std::marker::PhantomData, [])
+    }
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self as *const _ as *const T
+    }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self as *mut _ as *mut T
+    }
+    #[inline]
+    pub unsafe fn as_slice(&self, len: usize) -> &[T] {
+        ::std::slice::from_raw_parts(self.as_ptr(), len)
+    }
+    #[inline]
+    pub unsafe fn as_mut_slice(&mut self, len: usize) -> &mut [T] {
+        ::std::slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+    }
+}
+impl<T> ::std::fmt::Debug for __IncompleteArrayField<T> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        fmt.write_str("__IncompleteArrayField")
+    }
+}
+pub const SDS_MAX_PREALLOC: u32 = 1048576;
+pub const SDS_TYPE_5: u32 = 0;
+pub const SDS_TYPE_8: u32 = 1;
+pub const SDS_TYPE_16: u32 = 2;
+pub const SDS_TYPE_32: u32 = 3;
+pub const SDS_TYPE_64: u32 = 4;
+pub const SDS_TYPE_MASK: u32 = 7;
+pub const SDS_TYPE_BITS: u32 = 3;
+pub type sds = *mut ::std::os::raw::c_char;
+#[repr(C, packed)]
+pub struct sdshdr5 {
+    pub flags: ::std::os::raw::c_uchar,
+    pub buf: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of sdshdr5"][::std::mem::size_of::<sdshdr5>() - 1usize];
+    ["Alignment of sdshdr5"][::std::mem::align_of::<sdshdr5>() - 1usize];
+    ["Offset of field: sdshdr5::flags"][::std::mem::offset_of!(sdshdr5, flags) - 0usize];
+    ["Offset of field: sdshdr5::buf"][::std::mem::offset_of!(sdshdr5, buf) - 1usize];
+};
+#[repr(C, packed)]
+pub struct sdshdr8 {
+    pub len: u8,
+    pub alloc: u8,
+    pub flags: ::std::os::raw::c_uchar,
+    pub buf: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of sdshdr8"][::std::mem::size_of::<sdshdr8>() - 3usize];
+    ["Alignment of sdshdr8"][::std::mem::align_of::<sdshdr8>() - 1usize];
+    ["Offset of field: sdshdr8::len"][::std::mem::offset_of!(sdshdr8, len) - 0usize];
+    ["Offset of field: sdshdr8::alloc"][::std::mem::offset_of!(sdshdr8, alloc) - 1usize];
+    ["Offset of field: sdshdr8::flags"][::std::mem::offset_of!(sdshdr8, flags) - 2usize];
+    ["Offset of field: sdshdr8::buf"][::std::mem::offset_of!(sdshdr8, buf) - 3usize];
+};
+#[repr(C, packed)]
+pub struct sdshdr16 {
+    pub len: u16,
+    pub alloc: u16,
+    pub flags: ::std::os::raw::c_uchar,
+    pub buf: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of sdshdr16"][::std::mem::size_of::<sdshdr16>() - 5usize];
+    ["Alignment of sdshdr16"][::std::mem::align_of::<sdshdr16>() - 1usize];
+    ["Offset of field: sdshdr16::len"][::std::mem::offset_of!(sdshdr16, len) - 0usize];
+    ["Offset of field: sdshdr16::alloc"][::std::mem::offset_of!(sdshdr16, alloc) - 2usize];
+    ["Offset of field: sdshdr16::flags"][::std::mem::offset_of!(sdshdr16, flags) - 4usize];
+    ["Offset of field: sdshdr16::buf"][::std::mem::offset_of!(sdshdr16, buf) - 5usize];
+};
+#[repr(C, packed)]
+pub struct sdshdr32 {
+    pub len: u32,
+    pub alloc: u32,
+    pub flags: ::std::os::raw::c_uchar,
+    pub buf: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of sdshdr32"][::std::mem::size_of::<sdshdr32>() - 9usize];
+    ["Alignment of sdshdr32"][::std::mem::align_of::<sdshdr32>() - 1usize];
+    ["Offset of field: sdshdr32::len"][::std::mem::offset_of!(sdshdr32, len) - 0usize];
+    ["Offset of field: sdshdr32::alloc"][::std::mem::offset_of!(sdshdr32, alloc) - 4usize];
+    ["Offset of field: sdshdr32::flags"][::std::mem::offset_of!(sdshdr32, flags) - 8usize];
+    ["Offset of field: sdshdr32::buf"][::std::mem::offset_of!(sdshdr32, buf) - 9usize];
+};
+#[repr(C, packed)]
+pub struct sdshdr64 {
+    pub len: u64,
+    pub alloc: u64,
+    pub flags: ::std::os::raw::c_uchar,
+    pub buf: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of sdshdr64"][::std::mem::size_of::<sdshdr64>() - 17usize];
+    ["Alignment of sdshdr64"][::std::mem::align_of::<sdshdr64>() - 1usize];
+    ["Offset of field: sdshdr64::len"][::std::mem::offset_of!(sdshdr64, len) - 0usize];
+    ["Offset of field: sdshdr64::alloc"][::std::mem::offset_of!(sdshdr64, alloc) - 8usize];
+    ["Offset of field: sdshdr64::flags"][::std::mem::offset_of!(sdshdr64, flags) - 16usize];
+    ["Offset of field: sdshdr64::buf"][::std::mem::offset_of!(sdshdr64, buf) - 17usize];
+};
+extern "C" {
+    pub fn sdsnewlen(init: *const ::std::os::raw::c_void, initlen: usize) -> sds;
+}
+extern "C" {
+    pub fn sdsnew(init: *const ::std::os::raw::c_char) -> sds;
+}
+extern "C" {
+    pub fn sdsempty() -> sds;
+}
+extern "C" {
+    pub fn sdsdup(s: sds) -> sds;
+}
+extern "C" {
+    pub fn sdsfree(s: sds);
+}
+extern "C" {
+    pub fn sdsgrowzero(s: sds, len: usize) -> sds;
+}
+extern "C" {
+    pub fn sdscatlen(s: sds, t: *const ::std::os::raw::c_void, len: usize) -> sds;
+}
+extern "C" {
+    pub fn sdscat(s: sds, t: *const ::std::os::raw::c_char) -> sds;
+}
+extern "C" {
+    pub fn sdscatsds(s: sds, t: sds) -> sds;
+}
+extern "C" {
+    pub fn sdscpylen(s: sds, t: *const ::std::os::raw::c_char, len: usize) -> sds;
+}
+extern "C" {
+    pub fn sdscpy(s: sds, t: *const ::std::os::raw::c_char) -> sds;
+}
+extern "C" {
+    pub fn sdscatprintf(s: sds, fmt: *const ::std::os::raw::c_char, ...) -> sds;
+}
+extern "C" {
+    pub fn sdscatfmt(s: sds, fmt: *const ::std::os::raw::c_char, ...) -> sds;
+}
+extern "C" {
+    pub fn sdstrim(s: sds, cset: *const ::std::os::raw::c_char) -> sds;
+}
+extern "C" {
+    pub fn sdsrange(s: sds, start: ssize_t, end: ssize_t);
+}
+extern "C" {
+    pub fn sdsupdatelen(s: sds);
+}
+extern "C" {
+    pub fn sdsclear(s: sds);
+}
+extern "C" {
+    pub fn sdscmp(s1: sds, s2: sds) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sdssplitlen(
+        s: *const ::std::os::raw::c_char,
+        len: ssize_t,
+        sep: *const ::std::os::raw::c_char,
+        seplen: ::std::os::raw::c_int,
+        count: *mut ::std::os::raw::c_int,
+    ) -> *mut sds;
+}
+extern "C" {
+    pub fn sdsfreesplitres(tokens: *mut sds, count: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn sdstolower(s: sds);
+}
+extern "C" {
+    pub fn sdstoupper(s: sds);
+}
+extern "C" {
+    pub fn sdsfromlonglong(value: ::std::os::raw::c_longlong) -> sds;
+}
+extern "C" {
+    pub fn sdscatrepr(s: sds, p: *const ::std::os::raw::c_char, len: usize) -> sds;
+}
+extern "C" {
+    pub fn sdssplitargs(
+        line: *const ::std::os::raw::c_char,
+        argc: *mut ::std::os::raw::c_int,
+    ) -> *mut sds;
+}
+extern "C" {
+    pub fn sdsmapchars(
+        s: sds,
+        from: *const ::std::os::raw::c_char,
+        to: *const ::std::os::raw::c_char,
+        setlen: usize,
+    ) -> sds;
+}
+extern "C" {
+    pub fn sdsjoin(
+        argv: *mut *mut ::std::os::raw::c_char,
+        argc: ::std::os::raw::c_int,
+        sep: *mut ::std::os::raw::c_char,
+    ) -> sds;
+}
+extern "C" {
+    pub fn sdsjoinsds(
+        argv: *mut sds,
+        argc: ::std::os::raw::c_int,
+        sep: *const ::std::os::raw::c_char,
+        seplen: usize,
+    ) -> sds;
+}
+extern "C" {
+    pub fn sdsMakeRoomFor(s: sds, addlen: usize) -> sds;
+}
+extern "C" {
+    pub fn sdsIncrLen(s: sds, incr: ssize_t);
+}
+extern "C" {
+    pub fn sdsRemoveFreeSpace(s: sds) -> sds;
+}
+extern "C" {
+    pub fn sdsAllocSize(s: sds) -> usize;
+}
+extern "C" {
+    pub fn sdsAllocPtr(s: sds) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn sds_malloc(size: usize) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn sds_realloc(
+        ptr: *mut ::std::os::raw::c_void,
+        size: usize,
+    ) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn sds_free(ptr: *mut ::std::os::raw::c_void);
+}
@@ -1,8 +1,30 @@
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
 
+#[cfg(all(feature = "bindgen", not(feature = "pure-rust")))]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+#[cfg(all(
+    not(feature = "bindgen"),
+    not(feature = "pure-rust"),
+    sds_flavor = "jcorporation"
+))]
+include!("bindings.rs");
+#[cfg(all(not(feature = "bindgen"), sds_flavor = "antirez"))]
+include!("bindings_antirez.rs");
+#[cfg(all(not(feature = "bindgen"), sds_flavor = "redis7"))]
+include!("bindings_redis7.rs");
+#[cfg(feature = "pure-rust")]
+pub use sds_pure::*;
+
+// `static inline` in the antirez and Redis headers, so there is no symbol to
+// link. The headers are laid out the same in every flavor.
+#[cfg(any(sds_flavor = "antirez", sds_flavor = "redis7"))]
+pub use sds_pure::{sdsalloc, sdsavail, sdsinclen, sdslen, sdssetalloc, sdssetlen};
+
+/// The Rust port of `sds.c`, see the [`sds_pure`] crate.
+pub use sds_pure as pure;
 
 #[cfg(test)]
 mod tests {
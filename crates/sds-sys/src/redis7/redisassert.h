@@ -0,0 +1,11 @@
+/* Replacement for Redis's src/redisassert.h, used by the `redis7` flavor.
+ *
+ * Redis reports failed assertions through its own logging, here the plain
+ * libc assert is enough. */
+
+#ifndef __REDIS_ASSERT_H__
+#define __REDIS_ASSERT_H__
+
+#include <assert.h>
+
+#endif
@@ -0,0 +1,51 @@
+/* Replacement for Redis's src/sdsalloc.h, used by the `redis7` flavor.
+ *
+ * Redis routes SDS allocations through its zmalloc wrapper, which we don't
+ * build. This maps them to libc malloc, or to the functions exported by
+ * sds-pure when SDS_RUST_ALLOC is defined (the `rust-alloc` feature). The
+ * `_usable` variants report exactly the requested size, which is a valid
+ * lower bound of what the allocator handed out. */
+
+#ifndef __SDS_ALLOC_H__
+#define __SDS_ALLOC_H__
+
+#include <stddef.h>
+
+#ifdef SDS_RUST_ALLOC
+void *sds_rust_malloc(size_t size);
+void *sds_rust_realloc(void *ptr, size_t size);
+void sds_rust_free(void *ptr);
+
+#define s_malloc sds_rust_malloc
+#define s_realloc sds_rust_realloc
+#define s_free sds_rust_free
+#else
+#include <stdlib.h>
+
+#define s_malloc malloc
+#define s_realloc realloc
+#define s_free free
+#endif
+
+#define s_trymalloc s_malloc
+#define s_tryrealloc s_realloc
+
+static inline void *s_malloc_usable(size_t size, size_t *usable) {
+    if (usable) *usable = size;
+    return s_malloc(size);
+}
+
+static inline void *s_realloc_usable(void *ptr, size_t size, size_t *usable) {
+    if (usable) *usable = size;
+    return s_realloc(ptr, size);
+}
+
+static inline void s_free_usable(void *ptr, size_t *usable) {
+    if (usable) *usable = 0;
+    s_free(ptr);
+}
+
+#define s_trymalloc_usable s_malloc_usable
+#define s_tryrealloc_usable s_realloc_usable
+
+#endif
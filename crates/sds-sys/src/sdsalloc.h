@@ -0,0 +1,19 @@
+/* Replacement for sds/sdsalloc.h used by the `rust-alloc` feature.
+ *
+ * Every SDS allocation is forwarded to functions exported by sds-pure, which
+ * allocate through Rust's `#[global_allocator]`. */
+
+#ifndef __SDS_ALLOC_H__
+#define __SDS_ALLOC_H__
+
+#include <stddef.h>
+
+void *sds_rust_malloc(size_t size);
+void *sds_rust_realloc(void *ptr, size_t size);
+void sds_rust_free(void *ptr);
+
+#define s_malloc sds_rust_malloc
+#define s_realloc sds_rust_realloc
+#define s_free sds_rust_free
+
+#endif
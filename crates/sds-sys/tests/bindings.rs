@@ -0,0 +1,29 @@
+//! Checks that the checked-in `src/bindings.rs` hasn't drifted from what
+//! bindgen generates for the vendored `sds/sds.h`, or the
+//! `src/bindings_<flavor>.rs` of another flavor from its `sds.h`.
+//!
+//! Run with `cargo test -p sds-sys --features bindgen` (plus `antirez` or
+//! `redis7`).
+#![cfg(all(feature = "bindgen", sds_vendored))]
+
+#[test]
+fn checked_in_bindings_are_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+    #[cfg(sds_flavor = "jcorporation")]
+    let (path, checked_in) = ("src/bindings.rs", include_str!("../src/bindings.rs"));
+    #[cfg(sds_flavor = "antirez")]
+    let (path, checked_in) = (
+        "src/bindings_antirez.rs",
+        include_str!("../src/bindings_antirez.rs"),
+    );
+    #[cfg(sds_flavor = "redis7")]
+    let (path, checked_in) = (
+        "src/bindings_redis7.rs",
+        include_str!("../src/bindings_redis7.rs"),
+    );
+    assert!(
+        generated == checked_in,
+        "{path} is out of date, replace it with {}",
+        concat!(env!("OUT_DIR"), "/bindings.rs")
+    );
+}
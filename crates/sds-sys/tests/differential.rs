@@ -0,0 +1,379 @@
+//! Runs the same operations against the C libsds and the Rust port in
+//! `sds_sys::pure` and checks that both leave behind byte-for-byte identical
+//! strings, headers included.
+//!
+//! The port follows the jcorporation flavor, the others differ in which
+//! functions exist and how some of them grow or shrink strings.
+#![cfg(all(not(feature = "pure-rust"), sds_flavor = "jcorporation"))]
+
+use sds_sys::sds;
+use std::ffi::c_int;
+
+/// Runs `$body` once with the C functions in scope and once with the
+/// `sds_sys::pure` ones, asserts that both results are equal and returns it.
+macro_rules! same {
+    ($body:expr) => {{
+        #[allow(unused_unsafe)]
+        let c = unsafe {
+            use sds_sys::*;
+            $body
+        };
+        #[allow(unused_unsafe)]
+        let rust = unsafe {
+            use sds_sys::pure::*;
+            $body
+        };
+        assert_eq!(c, rust);
+        c
+    }};
+}
+
+/// The whole allocation of an SDS string up to and including its nul
+/// terminator: header, contents and terminator. Spare capacity after the
+/// terminator is left out since it is uninitialized.
+#[derive(Debug, PartialEq)]
+struct Snapshot(Vec<u8>);
+
+/// Decodes the header independently of either implementation.
+unsafe fn snapshot(s: sds) -> Snapshot {
+    let flags = *s.sub(1) as u8;
+    let (hdrlen, len) = match flags & 7 {
+        0 => (1, (flags >> 3) as usize),
+        1 => (3, *s.sub(3) as u8 as usize),
+        2 => (5, s.sub(5).cast::<u16>().read_unaligned() as usize),
+        3 => (9, s.sub(9).cast::<u32>().read_unaligned() as usize),
+        4 => (17, s.sub(17).cast::<u64>().read_unaligned() as usize),
+        _ => panic!("invalid flags {flags:#x}"),
+    };
+    Snapshot(std::slice::from_raw_parts(s.sub(hdrlen) as *const u8, hdrlen + len + 1).to_vec())
+}
+
+unsafe fn bytes(s: sds) -> Vec<u8> {
+    std::slice::from_raw_parts(s as *const u8, sds_sys::sdslen(s)).to_vec()
+}
+
+#[test]
+fn test_header_layouts() {
+    use std::mem::size_of;
+    assert_eq!(
+        size_of::<sds_sys::sdshdr5>(),
+        size_of::<sds_sys::pure::sdshdr5>()
+    );
+    assert_eq!(
+        size_of::<sds_sys::sdshdr8>(),
+        size_of::<sds_sys::pure::sdshdr8>()
+    );
+    assert_eq!(
+        size_of::<sds_sys::sdshdr16>(),
+        size_of::<sds_sys::pure::sdshdr16>()
+    );
+    assert_eq!(
+        size_of::<sds_sys::sdshdr32>(),
+        size_of::<sds_sys::pure::sdshdr32>()
+    );
+    assert_eq!(
+        size_of::<sds_sys::sdshdr64>(),
+        size_of::<sds_sys::pure::sdshdr64>()
+    );
+}
+
+#[test]
+fn test_newlen() {
+    let data = vec![b'x'; 70_000];
+    for len in [0, 1, 31, 32, 255, 256, 65_535, 65_536, 70_000] {
+        same!({
+            let s = sdsnewlen(data.as_ptr().cast(), len);
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+        same!({
+            let s = sdsnewlen(std::ptr::null(), len);
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+    }
+    same!({
+        let s = sdsnew(c"Hello".as_ptr());
+        let snapshot = snapshot(s);
+        sdsfree(s);
+        snapshot
+    });
+}
+
+#[test]
+fn test_catlen_growth() {
+    let chunk = [b'a'; 997];
+    same!({
+        let mut s = sdsempty();
+        let mut snapshots = Vec::new();
+        for _ in 0..100 {
+            s = sdscatlen(s, chunk.as_ptr().cast(), chunk.len());
+            snapshots.push((snapshot(s), sdsalloc(s), sdsavail(s), sdsAllocSize(s)));
+        }
+        sdsfree(s);
+        snapshots
+    });
+    same!({
+        let mut s = sdsnew(c"abc".as_ptr());
+        s = sdscat(s, c"def".as_ptr());
+        let t = sdsnew(c"ghi".as_ptr());
+        s = sdscatsds(s, t);
+        let snapshot = (snapshot(s), sdsalloc(s));
+        sdsfree(s);
+        sdsfree(t);
+        snapshot
+    });
+}
+
+#[test]
+fn test_make_room_and_shrink() {
+    for (len, add) in [
+        (0, 1),
+        (3, 30),
+        (31, 1),
+        (200, 100),
+        (60_000, 10_000),
+        (10, 2_000_000),
+    ] {
+        same!({
+            let mut s = sdsnewlen(std::ptr::null(), len);
+            s = sdsMakeRoomFor(s, add);
+            let grown = (snapshot(s), sdsalloc(s));
+            s = sdsRemoveFreeSpace(s);
+            let shrunk = (snapshot(s), sdsalloc(s));
+            sdsfree(s);
+            (grown, shrunk)
+        });
+    }
+    for (len, size) in [
+        (10, 10),
+        (10, 5),
+        (10, 300),
+        (300, 10),
+        (70_000, 100),
+        (5, 70_000),
+    ] {
+        same!({
+            let s = sdsResize(sdsnewlen(std::ptr::null(), len), size);
+            let resized = (snapshot(s), sdsalloc(s));
+            sdsfree(s);
+            resized
+        });
+    }
+}
+
+#[test]
+fn test_length_updates() {
+    same!({
+        let mut s = sdsnew(c"hello".as_ptr());
+        s = sdsMakeRoomFor(s, 10);
+        *s.add(5) = b'!' as _;
+        sdsIncrLen(s, 1);
+        let incremented = snapshot(s);
+        sdsIncrLen(s, -3);
+        let decremented = snapshot(s);
+        *s.add(1) = 0;
+        sdsupdatelen(s);
+        let updated = snapshot(s);
+        sdsclear(s);
+        let cleared = (snapshot(s), sdsavail(s));
+        s = sdsgrowzero(s, 40);
+        let grown = snapshot(s);
+        s = sdscpylen(s, c"copied".as_ptr(), 6);
+        let copied = snapshot(s);
+        s = sdscpy(s, c"copied again".as_ptr());
+        let copied_again = snapshot(s);
+        sdsfree(s);
+        (
+            incremented,
+            decremented,
+            updated,
+            cleared,
+            grown,
+            copied,
+            copied_again,
+        )
+    });
+}
+
+#[test]
+fn test_trim_range_substr() {
+    for cset in [c"xy", c" ", c"abc"] {
+        same!({
+            let s = sdsnewlen(b"xyxa\0bc yyx".as_ptr().cast(), 11);
+            let s = sdstrim(s, cset.as_ptr());
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+    }
+    for (start, end) in [
+        (0, -1),
+        (1, 3),
+        (-3, -1),
+        (5, 2),
+        (-100, 100),
+        (20, 30),
+        (0, 0),
+    ] {
+        same!({
+            let s = sdsnew(c"Hello World".as_ptr());
+            sdsrange(s, start, end);
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+    }
+    for (start, len) in [(0, 5), (6, 100), (11, 1), (100, 1), (3, 0)] {
+        same!({
+            let s = sdsnew(c"Hello World".as_ptr());
+            sdssubstr(s, start, len);
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+    }
+}
+
+#[test]
+fn test_mapping_and_comparison() {
+    same!({
+        let s = sdsnew(c"Hello World".as_ptr());
+        sdstolower(s);
+        let lower = bytes(s);
+        sdstoupper(s);
+        let upper = bytes(s);
+        let s = sdsmapchars(s, c"LO".as_ptr(), c"01".as_ptr(), 2);
+        let mapped = bytes(s);
+        sdsfree(s);
+        (lower, upper, mapped)
+    });
+    for (a, b) in [
+        (&b"abc"[..], &b"abd"[..]),
+        (b"abc", b"abc"),
+        (b"abcd", b"abc"),
+        (b"", b"a"),
+    ] {
+        same!({
+            let a = sdsnewlen(a.as_ptr().cast(), a.len());
+            let b = sdsnewlen(b.as_ptr().cast(), b.len());
+            let cmp = sdscmp(a, b).signum();
+            sdsfree(a);
+            sdsfree(b);
+            cmp
+        });
+    }
+    for value in [0, 1, -1, 42, i64::MAX, i64::MIN] {
+        same!({
+            let s = sdsfromlonglong(value);
+            let snapshot = snapshot(s);
+            sdsfree(s);
+            snapshot
+        });
+    }
+}
+
+#[test]
+fn test_repr() {
+    let data = b"a\\b\"c\n\r\t\x07\x08\x00\x7f\xff z";
+    same!({
+        let s = sdscatrepr(sdsempty(), data.as_ptr().cast(), data.len());
+        let repr = bytes(s);
+        sdsfree(s);
+        repr
+    });
+    for data in [&b"plain"[..], b"with space", b"quote\"", b"\xff"] {
+        same!({
+            let s = sdsnewlen(data.as_ptr().cast(), data.len());
+            let needs = sdsneedsrepr(s);
+            sdsfree(s);
+            needs
+        });
+    }
+}
+
+#[test]
+fn test_split_and_join() {
+    for (data, sep) in [
+        (&b"a,b,,c"[..], &b","[..]),
+        (b"foo_-_bar_-_", b"_-_"),
+        (b"no separator", b"|"),
+        (b",", b","),
+        (b"", b","),
+    ] {
+        same!({
+            let mut count: c_int = 0;
+            let tokens = sdssplitlen(
+                data.as_ptr().cast(),
+                data.len() as _,
+                sep.as_ptr().cast(),
+                sep.len() as c_int,
+                &mut count,
+            );
+            let split = (0..count as usize)
+                .map(|i| bytes(*tokens.add(i)))
+                .collect::<Vec<_>>();
+            sdsfreesplitres(tokens, count);
+            split
+        });
+    }
+    for line in [
+        c"set key value",
+        c"  \"quoted \\x41\\n\"  'single \\' quote'  ",
+        c"",
+        c"\"unterminated",
+        c"\"closed\"trailing",
+        c"'unterminated",
+    ] {
+        same!({
+            let mut argc: c_int = 0;
+            let argv = sdssplitargs(line.as_ptr(), &mut argc);
+            let args = (!argv.is_null()).then(|| {
+                (0..argc as usize)
+                    .map(|i| bytes(*argv.add(i)))
+                    .collect::<Vec<_>>()
+            });
+            sdsfreesplitres(argv, argc);
+            args
+        });
+    }
+    same!({
+        let mut parts = [
+            c"a".as_ptr().cast_mut(),
+            c"b".as_ptr().cast_mut(),
+            c"c".as_ptr().cast_mut(),
+        ];
+        let s = sdsjoin(parts.as_mut_ptr(), 3, c", ".as_ptr().cast_mut());
+        let joined = bytes(s);
+        sdsfree(s);
+        joined
+    });
+    same!({
+        let mut parts = [sdsnew(c"x".as_ptr()), sdsnewlen([0u8].as_ptr().cast(), 1)];
+        let s = sdsjoinsds(parts.as_mut_ptr(), 2, c"--".as_ptr(), 2);
+        let joined = bytes(s);
+        sdsfree(s);
+        parts.into_iter().for_each(|part| sdsfree(part));
+        joined
+    });
+}
+
+#[test]
+fn test_strings_cross_implementations() {
+    unsafe {
+        // Allocated by C, grown and freed by Rust.
+        let s = sds_sys::sdsnew(c"from C".as_ptr());
+        let s = sds_sys::pure::sdscatlen(s, b" and Rust".as_ptr().cast(), 9);
+        assert_eq!(bytes(s), b"from C and Rust");
+        sds_sys::pure::sdsfree(s);
+
+        // Allocated by Rust, grown and freed by C.
+        let s = sds_sys::pure::sdsnew(c"from Rust".as_ptr());
+        let s = sds_sys::sdscatlen(s, b" and C".as_ptr().cast(), 6);
+        let s = sds_sys::sdsRemoveFreeSpace(s);
+        assert_eq!(bytes(s), b"from Rust and C");
+        sds_sys::sdsfree(s);
+    }
+}
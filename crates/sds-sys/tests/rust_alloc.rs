@@ -0,0 +1,50 @@
+//! SDS allocations must go through the `#[global_allocator]` when built with
+//! the `rust-alloc` feature.
+//!
+//! A `harness = false` test: nothing else may allocate while `main` compares
+//! the counts, which the threads of the test harness would.
+
+use sds_sys::{sdsMakeRoomFor, sdsfree, sdsnewlen};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the bytes that are currently allocated.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_add(new_size, Ordering::SeqCst);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn main() {
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    let data = "Hello world!";
+    let sds = unsafe { sdsnewlen(data.as_ptr() as *const c_void, data.len()) };
+    let after_new = ALLOCATED.load(Ordering::SeqCst);
+    assert!(after_new > before + data.len());
+
+    let sds = unsafe { sdsMakeRoomFor(sds, 4096) };
+    assert!(ALLOCATED.load(Ordering::SeqCst) >= after_new + 4096);
+
+    unsafe { sdsfree(sds) };
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), before);
+}
@@ -0,0 +1,30 @@
+use std::env;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(sds_c_library)");
+    println!(
+        "cargo:rustc-check-cfg=cfg(sds_flavor, values(\"jcorporation\", \"antirez\", \"redis7\"))"
+    );
+
+    // Which variant of sds.c sds-sys binds, methods that need functions only
+    // some of them have are gated on `sds_flavor`.
+    let flavor = env::var("DEP_SDS_FLAVOR").unwrap_or_else(|_| "jcorporation".to_string());
+    println!("cargo:rustc-cfg=sds_flavor=\"{flavor}\"");
+
+    // The `links` metadata of sds-sys, forwarded so that tests can compile C
+    // code against the same libsds that we link. Unset with the `pure-rust`
+    // feature of sds-sys, in which case there is no C library at all.
+    if let Ok(include) = env::var("DEP_SDS_INCLUDE") {
+        println!("cargo:rustc-cfg=sds_c_library");
+        println!("cargo:rustc-env=DEP_SDS_INCLUDE={include}");
+        println!(
+            "cargo:rustc-env=DEP_SDS_LIB={}",
+            env::var("DEP_SDS_LIB").unwrap_or_default()
+        );
+        println!(
+            "cargo:rustc-env=DEP_SDS_FEATURES={}",
+            env::var("DEP_SDS_FEATURES").unwrap_or_default()
+        );
+    }
+    println!("cargo:rustc-env=TARGET={}", env::var("TARGET").unwrap());
+}
@@ -0,0 +1,325 @@
+//! Growing and shrinking the allocation of an [`SdsString`].
+//!
+//! The sds.h functions used here may move the string, they return the new
+//! pointer or NULL (leaving the old one alone) when the allocator fails.
+
+use crate::{c_sds, SdsString};
+use sds_sys::{
+    sdsIncrLen, sdsMakeRoomFor, sdsRemoveFreeSpace, sdsalloc, sdsavail, sdsempty, sdslen,
+    sdssetlen, ssize_t,
+};
+use std::alloc::{handle_alloc_error, Layout};
+use std::fmt::Display;
+use std::mem::MaybeUninit;
+
+/// The largest header, which is what libsds adds on top of the capacity in
+/// the worst case, plus the nul terminator.
+const OVERHEAD: usize = 17 + 1;
+
+impl SdsString {
+    /// Creates an empty SDS string with room for at least `capacity` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let s = SdsString::with_capacity(100);
+    /// assert!(s.is_empty());
+    /// assert!(s.capacity() >= 100);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut s = Self::from_new(unsafe { sdsempty() }, 0);
+        s.reserve_exact(capacity);
+        s
+    }
+
+    /// Returns the number of bytes the string can hold without reallocating,
+    /// not counting the nul terminator.
+    pub fn capacity(&self) -> usize {
+        unsafe { sdsalloc(self.0) }
+    }
+
+    /// Reserves room for at least `additional` more bytes, possibly more to
+    /// avoid frequent reallocations (libsds doubles the capacity up to 1 MiB
+    /// and then grows it 1 MiB at a time).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows, and aborts if the allocation
+    /// fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foo");
+    /// s.reserve(10);
+    /// assert!(s.capacity() >= 13);
+    /// assert_eq!(s.as_bytes(), b"foo");
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve(additional), self.len(), additional);
+    }
+
+    /// Reserves room for `additional` more bytes, without deliberately
+    /// over-allocating.
+    ///
+    /// With the antirez flavor of libsds, which has no way to grow a string
+    /// by an exact amount, this is the same as [`SdsString::reserve`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows, and aborts if the allocation
+    /// fails.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        handle_reserve(
+            self.try_reserve_with(additional, make_room_for_exact),
+            self.len(),
+            additional,
+        );
+    }
+
+    /// Tries to reserve room for at least `additional` more bytes like
+    /// [`SdsString::reserve`], returning an error instead of panicking or
+    /// aborting. The string is unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{SdsString, TryReserveError};
+    ///
+    /// let mut s = SdsString::new("foo");
+    /// assert_eq!(s.try_reserve(10), Ok(()));
+    /// assert_eq!(
+    ///     s.try_reserve(usize::MAX),
+    ///     Err(TryReserveError::CapacityOverflow)
+    /// );
+    /// assert_eq!(s.as_bytes(), b"foo");
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve_with(additional, |s, additional| unsafe {
+            sdsMakeRoomFor(s, additional)
+        })
+    }
+
+    fn try_reserve_with(
+        &mut self,
+        additional: usize,
+        make_room_for: impl FnOnce(c_sds, usize) -> c_sds,
+    ) -> Result<(), TryReserveError> {
+        if unsafe { sdsavail(self.0) } >= additional {
+            return Ok(());
+        }
+        let len = unsafe { sdslen(self.0) };
+        if len
+            .checked_add(additional)
+            .is_none_or(|capacity| capacity > isize::MAX as usize - OVERHEAD)
+        {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+        let s = make_room_for(self.0, additional);
+        if s.is_null() {
+            return Err(TryReserveError::AllocError);
+        }
+        self.0 = s;
+        Ok(())
+    }
+
+    /// Returns the free space at the end of the string, which may be
+    /// uninitialized.
+    ///
+    /// After writing to it, make the bytes part of the string with
+    /// [`SdsString::advance_len`] or [`SdsString::set_len`]. The room for the
+    /// nul terminator is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::with_capacity(10);
+    /// let spare = s.spare_capacity_mut();
+    /// spare[0].write(b'h');
+    /// spare[1].write(b'i');
+    /// unsafe { s.advance_len(2) };
+    /// assert_eq!(s.as_bytes_with_nul(), b"hi\0");
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        unsafe {
+            let len = sdslen(self.0);
+            std::slice::from_raw_parts_mut(self.0.add(len).cast(), sdsavail(self.0))
+        }
+    }
+
+    /// Adds `n` bytes of the spare capacity to the end of the string and
+    /// writes a new nul terminator, like `sdsIncrLen`.
+    ///
+    /// # Safety
+    ///
+    /// `n` must not exceed [`SdsStr::avail`](crate::SdsStr::avail) and the
+    /// first `n` bytes of [`SdsString::spare_capacity_mut`] must be
+    /// initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// // Short strings have no spare capacity, advancing by 0 is still fine.
+    /// let mut s = SdsString::new("ab");
+    /// unsafe { s.advance_len(0) };
+    /// assert_eq!(s.as_bytes_with_nul(), b"ab\0");
+    /// ```
+    pub unsafe fn advance_len(&mut self, n: usize) {
+        debug_assert!(n <= sdsavail(self.0));
+        // sdsIncrLen asserts that a type 5 string grows, even by nothing.
+        if n == 0 {
+            return;
+        }
+        sdsIncrLen(self.0, n as ssize_t);
+    }
+
+    /// Sets the length of the string to `new_len` and writes a new nul
+    /// terminator, like `sdssetlen`.
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must not exceed [`SdsString::capacity`] and the bytes up to
+    /// it must be initialized.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= sdsalloc(self.0));
+        sdssetlen(self.0, new_len);
+        *self.0.add(new_len) = 0;
+    }
+
+    /// Shrinks the capacity of the string to its length.
+    ///
+    /// Does nothing if the allocator fails to shrink it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::with_capacity(100);
+    /// s.shrink_to_fit();
+    /// assert_eq!(s.capacity(), 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let s = unsafe { remove_free_space(self.0) };
+        if !s.is_null() {
+            self.0 = s;
+        }
+    }
+
+    /// Shrinks the capacity of the string to `min_capacity`, or its length if
+    /// that is greater. Does nothing if the capacity is already lower.
+    ///
+    /// Does nothing if the allocator fails to shrink it. Not available with
+    /// the antirez flavor of libsds, which has no `sdsResize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::with_capacity(100);
+    /// s.shrink_to(10);
+    /// assert_eq!(s.capacity(), 10);
+    /// s.shrink_to(20);
+    /// assert_eq!(s.capacity(), 10);
+    /// ```
+    #[cfg(not(sds_flavor = "antirez"))]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let capacity = min_capacity.max(self.len());
+        if capacity >= self.capacity() {
+            return;
+        }
+        let s = unsafe { resize(self.0, capacity) };
+        if !s.is_null() {
+            self.0 = s;
+        }
+    }
+}
+
+impl SdsString {
+    /// Stores the new pointer returned by an sds.h function that grows the
+    /// string to `len` bytes, aborting if it is NULL.
+    pub(crate) fn set_grown(&mut self, s: c_sds, len: usize) {
+        self.0 = check_alloc(s, len);
+    }
+
+    /// Wraps the pointer returned by an sds.h function that creates a string
+    /// of `len` bytes, aborting if it is NULL.
+    pub(crate) fn from_new(s: c_sds, len: usize) -> Self {
+        Self(check_alloc(s, len))
+    }
+}
+
+fn check_alloc(s: c_sds, len: usize) -> c_sds {
+    if s.is_null() {
+        handle_alloc_error(Layout::array::<u8>(len.saturating_add(OVERHEAD)).unwrap());
+    }
+    s
+}
+
+fn handle_reserve(result: Result<(), TryReserveError>, len: usize, additional: usize) {
+    match result {
+        Ok(()) => {}
+        Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveError::AllocError) => {
+            handle_alloc_error(Layout::array::<u8>(len + additional + OVERHEAD).unwrap())
+        }
+    }
+}
+
+fn make_room_for_exact(s: c_sds, additional: usize) -> c_sds {
+    unsafe {
+        #[cfg(sds_flavor = "jcorporation")]
+        return sds_sys::sdsResize(s, sdslen(s) + additional);
+        #[cfg(sds_flavor = "redis7")]
+        return sds_sys::sdsMakeRoomForNonGreedy(s, additional);
+        #[cfg(sds_flavor = "antirez")]
+        return sdsMakeRoomFor(s, additional);
+    }
+}
+
+unsafe fn remove_free_space(s: c_sds) -> c_sds {
+    #[cfg(sds_flavor = "redis7")]
+    return sdsRemoveFreeSpace(s, 0);
+    #[cfg(not(sds_flavor = "redis7"))]
+    return sdsRemoveFreeSpace(s);
+}
+
+#[cfg(not(sds_flavor = "antirez"))]
+unsafe fn resize(s: c_sds, size: usize) -> c_sds {
+    #[cfg(sds_flavor = "redis7")]
+    return sds_sys::sdsResize(s, size, 0);
+    #[cfg(not(sds_flavor = "redis7"))]
+    return sds_sys::sdsResize(s, size);
+}
+
+/// The error type for [`SdsString::try_reserve`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TryReserveError {
+    /// The new capacity doesn't fit in an allocation.
+    CapacityOverflow,
+    /// The allocator of libsds returned NULL.
+    AllocError,
+}
+
+impl Display for TryReserveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("memory allocation failed")?;
+        match self {
+            Self::CapacityOverflow => {
+                f.write_str(" because the computed capacity exceeded the collection's maximum")
+            }
+            Self::AllocError => f.write_str(" because the memory allocator returned an error"),
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
@@ -0,0 +1,221 @@
+//! [`Read`], [`BufRead`], [`Seek`] and [`Write`] over SDS strings, like
+//! [`std::io::Cursor`] does for slices and vectors.
+
+use crate::{SdsStr, SdsString};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+/// A reading cursor over a borrowed [`SdsStr`].
+///
+/// # Examples
+///
+/// ```
+/// use sds::{SdsCursor, SdsString};
+/// use std::io::{BufRead, Read, Seek, SeekFrom};
+///
+/// let s = SdsString::new("first\nsecond\n");
+/// let mut cursor = SdsCursor::new(&s);
+/// let mut line = String::new();
+/// cursor.read_line(&mut line).unwrap();
+/// assert_eq!(line, "first\n");
+///
+/// cursor.seek(SeekFrom::End(-4)).unwrap();
+/// let mut rest = Vec::new();
+/// cursor.read_to_end(&mut rest).unwrap();
+/// assert_eq!(rest, b"ond\n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SdsCursor<'a> {
+    inner: &'a SdsStr,
+    pos: u64,
+}
+
+impl<'a> SdsCursor<'a> {
+    /// Creates a cursor at the start of `inner`.
+    pub fn new(inner: &'a SdsStr) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Returns the string this cursor reads.
+    pub fn get_ref(&self) -> &'a SdsStr {
+        self.inner
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the current position, which may be past the end.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl Read for SdsCursor<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = read(self.inner.as_bytes(), self.pos, buf);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl BufRead for SdsCursor<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(remaining(self.inner.as_bytes(), self.pos))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl Seek for SdsCursor<'_> {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        self.pos = seek(self.pos, self.inner.len(), style)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// A cursor that owns an [`SdsString`], for reading and for writing.
+///
+/// Writes overwrite the string in place and append past its end. Seeking
+/// past the end and then writing fills the gap with zeroes, through
+/// `sdsgrowzero`.
+///
+/// # Examples
+///
+/// ```
+/// use sds::{SdsString, SdsStringCursor};
+/// use std::io::{Seek, SeekFrom, Write};
+///
+/// let mut cursor = SdsStringCursor::new(SdsString::new("hello"));
+/// cursor.write_all(b"J").unwrap();
+/// cursor.seek(SeekFrom::End(2)).unwrap();
+/// cursor.write_all(b"!").unwrap();
+/// assert_eq!(cursor.into_inner().as_bytes(), b"Jello\0\0!");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SdsStringCursor {
+    inner: SdsString,
+    pos: u64,
+}
+
+impl SdsStringCursor {
+    /// Creates a cursor at the start of `inner`.
+    pub fn new(inner: SdsString) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Returns the string, dropping the cursor.
+    pub fn into_inner(self) -> SdsString {
+        self.inner
+    }
+
+    /// Returns a reference to the string.
+    pub fn get_ref(&self) -> &SdsString {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the string. The position is left
+    /// alone when it changes.
+    pub fn get_mut(&mut self) -> &mut SdsString {
+        &mut self.inner
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the current position, which may be past the end.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl Read for SdsStringCursor {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = read(self.inner.as_bytes(), self.pos, buf);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl BufRead for SdsStringCursor {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(remaining(self.inner.as_bytes(), self.pos))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl Seek for SdsStringCursor {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        self.pos = seek(self.pos, self.inner.len(), style)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl Write for SdsStringCursor {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let pos = usize::try_from(self.pos)
+            .ok()
+            .filter(|pos| pos.checked_add(buf.len()).is_some())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cursor position exceeds maximum possible string length",
+                )
+            })?;
+        let len = self.inner.len();
+        if pos > len {
+            self.inner.resize(pos, 0);
+        }
+        let overwritten = buf.len().min(self.inner.len() - pos);
+        self.inner.as_bytes_mut()[pos..pos + overwritten].copy_from_slice(&buf[..overwritten]);
+        self.inner.extend_from_slice(&buf[overwritten..]);
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The bytes of `bytes` from `pos` on, empty if `pos` is past the end.
+fn remaining(bytes: &[u8], pos: u64) -> &[u8] {
+    let start = pos.min(bytes.len() as u64) as usize;
+    &bytes[start..]
+}
+
+fn read(bytes: &[u8], pos: u64, buf: &mut [u8]) -> usize {
+    let remaining = remaining(bytes, pos);
+    let n = remaining.len().min(buf.len());
+    buf[..n].copy_from_slice(&remaining[..n]);
+    n
+}
+
+fn seek(pos: u64, len: usize, style: SeekFrom) -> io::Result<u64> {
+    let (base, offset) = match style {
+        SeekFrom::Start(n) => return Ok(n),
+        SeekFrom::End(n) => (len as u64, n),
+        SeekFrom::Current(n) => (pos, n),
+    };
+    base.checked_add_signed(offset).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
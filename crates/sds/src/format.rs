@@ -0,0 +1,304 @@
+//! Formatting into new and existing [`SdsString`]s: `sds_format!` and
+//! `sds_write!` take the syntax of `format!`, `sds_catfmt!` the one of
+//! `sdscatfmt`.
+
+use crate::SdsString;
+use std::fmt::{self, Write};
+
+/// Creates an [`SdsString`] from formatting arguments, like
+/// [`std::fmt::format`] does for a [`String`].
+///
+/// Usually called through [`sds_format!`](crate::sds_format).
+///
+/// # Panics
+///
+/// Panics if a formatting trait implementation returns an error.
+pub fn format(args: fmt::Arguments<'_>) -> SdsString {
+    match args.as_str() {
+        Some(s) => SdsString::new(s),
+        None => {
+            let mut s = SdsString::new("");
+            s.write_fmt(args)
+                .expect("a formatting trait implementation returned an error");
+            s
+        }
+    }
+}
+
+/// Creates an [`SdsString`] using interpolation of runtime expressions, with
+/// the syntax of [`format!`].
+///
+/// # Panics
+///
+/// Panics if a formatting trait implementation returns an error.
+///
+/// # Examples
+///
+/// ```
+/// use sds::sds_format;
+///
+/// let s = sds_format!("*{}\r\n", 3);
+/// assert_eq!(s.as_bytes(), b"*3\r\n");
+/// ```
+#[macro_export]
+macro_rules! sds_format {
+    ($($arg:tt)*) => {
+        $crate::format(::std::format_args!($($arg)*))
+    };
+}
+
+/// Appends formatted text to an [`SdsString`], with the syntax of [`write!`].
+///
+/// `$dst` is an `SdsString` or a mutable reference to one. Unlike with
+/// `write!` there is no [`Result`] to unwrap, appending can't fail.
+///
+/// # Panics
+///
+/// Panics if a formatting trait implementation returns an error.
+///
+/// # Examples
+///
+/// ```
+/// use sds::{sds_write, SdsString};
+///
+/// let mut s = SdsString::new("$");
+/// sds_write!(s, "{}\r\n", 5);
+/// sds_write!(&mut s, "{}\r\n", "hello");
+/// assert_eq!(s.as_bytes(), b"$5\r\nhello\r\n");
+/// ```
+#[macro_export]
+macro_rules! sds_write {
+    ($dst:expr, $($arg:tt)*) => {{
+        use $crate::__private::SdsWrite as _;
+        $dst.__sds_write_fmt(::std::format_args!($($arg)*))
+    }};
+}
+
+/// Appends to an [`SdsString`] with the C function `sdscatfmt`, whose format
+/// string is checked against the arguments at compile time.
+///
+/// `sdscatfmt` knows these specifiers, each taking arguments of the types
+/// implementing [`CatFmtArg`] for it:
+///
+/// | Specifier | C type               | Rust types              |
+/// |-----------|----------------------|-------------------------|
+/// | `%s`      | `char *`             | `&CStr`                 |
+/// | `%S`      | `sds`                | `&SdsStr`, `&SdsString` |
+/// | `%i`      | `int`                | `i32`                   |
+/// | `%I`      | `long long`          | `i64`                   |
+/// | `%u`      | `unsigned int`       | `u32`                   |
+/// | `%U`      | `unsigned long long` | `u64`                   |
+/// | `%%`      |                      | none, appends a `%`     |
+///
+/// Any other specifier, a `%` at the end of the format string or a number of
+/// arguments that doesn't match the specifiers is a compile error. Integers
+/// aren't widened, a `usize` has to be cast to `u64` for `%U`.
+///
+/// `$dst` is an `SdsString` or a mutable reference to one. Only available
+/// when sds-sys links a C libsds, the `pure-rust` port has no varargs
+/// functions.
+///
+/// # Examples
+///
+/// ```
+/// use sds::{sds_catfmt, SdsString};
+///
+/// let key = SdsString::new("counter");
+/// let mut s = SdsString::new("");
+/// sds_catfmt!(s, "%s %S=%I (%u%%)", c"INCR", &key, -1, 50);
+/// assert_eq!(s.as_bytes(), b"INCR counter=-1 (50%)");
+/// ```
+///
+/// Arguments of the wrong type don't compile:
+///
+/// ```compile_fail
+/// use sds::{sds_catfmt, SdsString};
+///
+/// let mut s = SdsString::new("");
+/// sds_catfmt!(s, "%i", 1u64);
+/// ```
+///
+/// and neither do missing ones:
+///
+/// ```compile_fail
+/// use sds::{sds_catfmt, SdsString};
+///
+/// let mut s = SdsString::new("");
+/// sds_catfmt!(s, "%s and %s", c"one");
+/// ```
+#[cfg(sds_c_library)]
+#[macro_export]
+macro_rules! sds_catfmt {
+    // Converts the arguments one by one, binding each to its own (hygienic)
+    // `raw` so that no caller expression ends up in the `unsafe` block.
+    (@args $dst:expr; $idx:expr; [$($raw:ident)*]; $arg:expr, $($rest:expr,)*) => {
+        match $crate::__private::catfmt_arg::<{ SPECS[$idx] }, _>($arg) {
+            raw => $crate::sds_catfmt!(@args $dst; $idx + 1; [$($raw)* raw]; $($rest,)*),
+        }
+    };
+    (@args $dst:expr; $idx:expr; [$($raw:ident)*];) => {{
+        use $crate::__private::SdsWrite as _;
+        $dst.__sds_catfmt(|s| unsafe { $crate::__private::sdscatfmt(s, FMT.as_ptr() $(, $raw)*) })
+    }};
+    ($dst:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        const FMT: &::std::ffi::CStr =
+            $crate::__private::catfmt_cstr(::std::concat!($fmt, "\0").as_bytes());
+        const SPECS: [u8; 0 $(+ $crate::__private::one(::std::stringify!($arg)))*] =
+            $crate::__private::catfmt_specs(FMT);
+        $crate::sds_catfmt!(@args $dst; 0; []; $($arg,)*)
+    }};
+}
+
+/// A type that can be passed to [`sds_catfmt!`](crate::sds_catfmt) for the
+/// specifier `%SPEC`, for instance `CatFmtArg<b'I'>` for `%I`.
+///
+/// # Safety
+///
+/// [`CatFmtArg::Raw`] has to be what `sdscatfmt` reads with `va_arg` for the
+/// specifier, and a pointer has to be valid for as long as `self` is.
+#[cfg(sds_c_library)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be formatted by this `sdscatfmt` specifier",
+    label = "wrong type for the specifier",
+    note = "see the documentation of `sds_catfmt!` for the types of each specifier"
+)]
+pub unsafe trait CatFmtArg<const SPEC: u8> {
+    /// The C type passed to `sdscatfmt`.
+    type Raw;
+
+    /// Converts `self` to what is passed to `sdscatfmt`.
+    fn into_raw(self) -> Self::Raw;
+}
+
+#[cfg(sds_c_library)]
+mod catfmt_args {
+    use super::CatFmtArg;
+    use crate::{SdsStr, SdsString};
+    use std::ffi::{c_char, c_int, c_longlong, c_uint, c_ulonglong, CStr};
+
+    macro_rules! impl_cat_fmt_arg {
+        ($spec:literal => $raw:ty: $ty:ty) => {
+            unsafe impl CatFmtArg<$spec> for $ty {
+                type Raw = $raw;
+
+                fn into_raw(self) -> $raw {
+                    self as $raw
+                }
+            }
+        };
+    }
+
+    impl_cat_fmt_arg!(b'i' => c_int: i32);
+    impl_cat_fmt_arg!(b'I' => c_longlong: i64);
+    impl_cat_fmt_arg!(b'u' => c_uint: u32);
+    impl_cat_fmt_arg!(b'U' => c_ulonglong: u64);
+
+    unsafe impl CatFmtArg<b's'> for &CStr {
+        type Raw = *const c_char;
+
+        fn into_raw(self) -> *const c_char {
+            self.as_ptr()
+        }
+    }
+
+    unsafe impl CatFmtArg<b'S'> for &SdsStr {
+        type Raw = *const c_char;
+
+        fn into_raw(self) -> *const c_char {
+            self.as_ptr()
+        }
+    }
+
+    unsafe impl CatFmtArg<b'S'> for &SdsString {
+        type Raw = *const c_char;
+
+        fn into_raw(self) -> *const c_char {
+            self.as_ptr()
+        }
+    }
+}
+
+/// Used by the macros, not public API.
+#[doc(hidden)]
+pub mod private {
+    use crate::SdsString;
+    use std::fmt::{self, Write};
+
+    #[cfg(sds_c_library)]
+    pub use sds_sys::sdscatfmt;
+
+    /// Lets the macros take an `SdsString` or a `&mut SdsString` through
+    /// auto-referencing.
+    pub trait SdsWrite {
+        fn __sds_write_fmt(&mut self, args: fmt::Arguments<'_>);
+
+        #[cfg(sds_c_library)]
+        fn __sds_catfmt(&mut self, f: impl FnOnce(crate::c_sds) -> crate::c_sds);
+    }
+
+    impl SdsWrite for SdsString {
+        fn __sds_write_fmt(&mut self, args: fmt::Arguments<'_>) {
+            self.write_fmt(args)
+                .expect("a formatting trait implementation returned an error");
+        }
+
+        #[cfg(sds_c_library)]
+        fn __sds_catfmt(&mut self, f: impl FnOnce(crate::c_sds) -> crate::c_sds) {
+            let len = self.len();
+            let s = f(self.0);
+            self.set_grown(s, len);
+        }
+    }
+
+    #[cfg(sds_c_library)]
+    pub const fn one(_arg: &str) -> usize {
+        1
+    }
+
+    #[cfg(sds_c_library)]
+    pub const fn catfmt_cstr(fmt: &[u8]) -> &std::ffi::CStr {
+        match std::ffi::CStr::from_bytes_with_nul(fmt) {
+            Ok(fmt) => fmt,
+            Err(_) => panic!("sds_catfmt!: the format string contains a nul byte"),
+        }
+    }
+
+    /// Returns the letter of each specifier of `fmt` that takes an argument,
+    /// failing to compile if there aren't `N` of them.
+    #[cfg(sds_c_library)]
+    pub const fn catfmt_specs<const N: usize>(fmt: &std::ffi::CStr) -> [u8; N] {
+        let fmt = fmt.to_bytes();
+        let mut specs = [0; N];
+        let mut count = 0;
+        let mut i = 0;
+        while i < fmt.len() {
+            if fmt[i] == b'%' {
+                i += 1;
+                if i == fmt.len() {
+                    panic!("sds_catfmt!: the format string ends with a `%`");
+                }
+                match fmt[i] {
+                    b'%' => {}
+                    b's' | b'S' | b'i' | b'I' | b'u' | b'U' => {
+                        if count == N {
+                            panic!("sds_catfmt!: more specifiers than arguments");
+                        }
+                        specs[count] = fmt[i];
+                        count += 1;
+                    }
+                    _ => panic!("sds_catfmt!: unknown specifier, expected one of `%s`, `%S`, `%i`, `%I`, `%u`, `%U` or `%%`"),
+                }
+            }
+            i += 1;
+        }
+        if count != N {
+            panic!("sds_catfmt!: more arguments than specifiers");
+        }
+        specs
+    }
+
+    #[cfg(sds_c_library)]
+    pub fn catfmt_arg<const SPEC: u8, T: super::CatFmtArg<SPEC>>(arg: T) -> T::Raw {
+        arg.into_raw()
+    }
+}
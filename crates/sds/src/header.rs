@@ -0,0 +1,216 @@
+//! What libsds stores in front of every string.
+
+use crate::SdsStr;
+use sds_sys::{
+    sdsAllocPtr, sdsAllocSize, sdsalloc, sdsavail, SDS_TYPE_16, SDS_TYPE_32, SDS_TYPE_5,
+    SDS_TYPE_64, SDS_TYPE_8, SDS_TYPE_MASK,
+};
+
+/// The kind of header of an SDS string, one of the `SDS_TYPE_*` constants.
+///
+/// libsds picks the smallest header whose fields can hold the length of the
+/// string when it is created, and switches to a bigger one when it grows.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum SdsType {
+    /// `sdshdr5`: the length lives in the flags byte and there is no
+    /// capacity. Only used for short strings that are never grown.
+    Type5,
+    /// `sdshdr8`: 8-bit length and capacity.
+    Type8,
+    /// `sdshdr16`: 16-bit length and capacity.
+    Type16,
+    /// `sdshdr32`: 32-bit length and capacity.
+    Type32,
+    /// `sdshdr64`: 64-bit length and capacity.
+    Type64,
+}
+
+impl SdsType {
+    /// Decodes the type bits of a header's flags byte.
+    ///
+    /// Returns [`None`] if they don't name a known header type.
+    pub const fn from_flags(flags: u8) -> Option<Self> {
+        match flags as u32 & SDS_TYPE_MASK {
+            SDS_TYPE_5 => Some(Self::Type5),
+            SDS_TYPE_8 => Some(Self::Type8),
+            SDS_TYPE_16 => Some(Self::Type16),
+            SDS_TYPE_32 => Some(Self::Type32),
+            SDS_TYPE_64 => Some(Self::Type64),
+            _ => None,
+        }
+    }
+
+    /// Returns the size of this kind of header in bytes, flags included.
+    pub const fn header_size(self) -> usize {
+        match self {
+            Self::Type5 => 1,
+            Self::Type8 => 3,
+            Self::Type16 => 5,
+            Self::Type32 => 9,
+            Self::Type64 => 17,
+        }
+    }
+}
+
+/// A copy of the header of an SDS string, see [`SdsStr::header`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SdsHeader {
+    /// Which of the header structs this is.
+    pub kind: SdsType,
+    /// The length of the string in bytes, without the nul terminator.
+    pub len: usize,
+    /// The capacity of the string in bytes, without the header and the nul
+    /// terminator. Equal to `len` for [`SdsType::Type5`].
+    pub alloc: usize,
+    /// The raw flags byte. The low 3 bits are the type, for
+    /// [`SdsType::Type5`] the upper 5 bits are the length.
+    pub flags: u8,
+}
+
+impl SdsStr {
+    /// Reads the header in front of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{SdsString, SdsType};
+    ///
+    /// let s = SdsString::new("foo");
+    /// let header = s.header();
+    /// assert_eq!(header.kind, SdsType::Type5);
+    /// assert_eq!(header.len, 3);
+    /// assert_eq!(header.alloc, 3);
+    ///
+    /// let s = SdsString::new(vec![b'x'; 300]);
+    /// assert_eq!(s.header().kind, SdsType::Type16);
+    /// ```
+    pub fn header(&self) -> SdsHeader {
+        let ptr = self.as_ptr().cast_mut();
+        let flags = unsafe { *ptr.sub(1) } as u8;
+        SdsHeader {
+            kind: SdsType::from_flags(flags).expect("invalid SDS header flags"),
+            len: self.len(),
+            alloc: unsafe { sdsalloc(ptr) },
+            flags,
+        }
+    }
+
+    /// Returns the size of the header in front of this string in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// assert_eq!(SdsString::new("foo").header_size(), 1);
+    /// assert_eq!(SdsString::new(vec![b'x'; 300]).header_size(), 5);
+    /// ```
+    pub fn header_size(&self) -> usize {
+        let ptr = self.as_ptr().cast_mut();
+        unsafe { ptr.offset_from(sdsAllocPtr(ptr).cast()) as usize }
+    }
+
+    /// Returns the size of the whole allocation of this string in bytes:
+    /// header, capacity and nul terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let s = SdsString::new("foo");
+    /// assert_eq!(s.alloc_size(), s.header_size() + s.header().alloc + 1);
+    /// ```
+    pub fn alloc_size(&self) -> usize {
+        unsafe { sdsAllocSize(self.as_ptr().cast_mut()) }
+    }
+
+    /// Returns how many bytes can be appended to this string before libsds
+    /// has to reallocate it.
+    pub fn avail(&self) -> usize {
+        unsafe { sdsavail(self.as_ptr().cast_mut()) }
+    }
+}
+
+/// An error returned by [`SdsStr::try_from_ptr`] and
+/// [`SdsString::try_from_raw`](crate::SdsString::try_from_raw) for pointers
+/// that can't be SDS strings.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InvalidSdsError {
+    /// The pointer is null.
+    Null,
+    /// The flags byte in front of the string isn't one libsds writes: its
+    /// type bits don't name a header type, or they name one other than
+    /// [`SdsType::Type5`] and the bits above them aren't zero. It is most
+    /// likely a plain C string.
+    UnknownType {
+        /// The byte right before the string.
+        flags: u8,
+    },
+    /// The header claims a length greater than the capacity.
+    LenExceedsAlloc { len: usize, alloc: usize },
+    /// There is no nul byte at the position given by the length.
+    NotNulTerminated { len: usize },
+}
+
+impl std::fmt::Display for InvalidSdsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => f.write_str("null pointer"),
+            Self::UnknownType { flags } => {
+                write!(f, "unknown SDS header type in flags {flags:#04x}")
+            }
+            Self::LenExceedsAlloc { len, alloc } => {
+                write!(f, "SDS length {len} exceeds the capacity of {alloc}")
+            }
+            Self::NotNulTerminated { len } => {
+                write!(f, "SDS string of length {len} is not nul terminated")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidSdsError {}
+
+/// Decodes the header in front of `ptr` without going through libsds and
+/// checks that it is consistent. Returns the length of the string.
+///
+/// # Safety
+///
+/// `ptr` must be null or readable from the start of its header (as decoded
+/// from the byte before it) up to the length in that header.
+pub(crate) unsafe fn check(ptr: *const std::ffi::c_char) -> Result<usize, InvalidSdsError> {
+    if ptr.is_null() {
+        return Err(InvalidSdsError::Null);
+    }
+    let flags = *ptr.sub(1) as u8;
+    let kind = SdsType::from_flags(flags).ok_or(InvalidSdsError::UnknownType { flags })?;
+    // Only sdshdr5 keeps something in the bits above the type.
+    if kind != SdsType::Type5 && flags as u32 & !SDS_TYPE_MASK != 0 {
+        return Err(InvalidSdsError::UnknownType { flags });
+    }
+    let fields = ptr.sub(kind.header_size());
+    let (len, alloc) = match kind {
+        SdsType::Type5 => ((flags >> 3) as usize, (flags >> 3) as usize),
+        SdsType::Type8 => (*fields as u8 as usize, *fields.add(1) as u8 as usize),
+        SdsType::Type16 => (
+            fields.cast::<u16>().read_unaligned() as usize,
+            fields.add(2).cast::<u16>().read_unaligned() as usize,
+        ),
+        SdsType::Type32 => (
+            fields.cast::<u32>().read_unaligned() as usize,
+            fields.add(4).cast::<u32>().read_unaligned() as usize,
+        ),
+        SdsType::Type64 => (
+            fields.cast::<u64>().read_unaligned() as usize,
+            fields.add(8).cast::<u64>().read_unaligned() as usize,
+        ),
+    };
+    if len > alloc {
+        return Err(InvalidSdsError::LenExceedsAlloc { len, alloc });
+    }
+    if *ptr.add(len) != 0 {
+        return Err(InvalidSdsError::NotNulTerminated { len });
+    }
+    Ok(len)
+}
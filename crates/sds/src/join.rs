@@ -0,0 +1,211 @@
+//! Building an [`SdsString`] out of many parts.
+
+use crate::{SdsStr, SdsString};
+use sds_sys::{sdsjoin, sdsjoinsds};
+use std::ffi::{c_int, CStr};
+use std::ops::{Add, AddAssign};
+
+impl SdsString {
+    /// Joins `parts` with `sep` between each of them.
+    ///
+    /// The parts are collected first so that the string is allocated once,
+    /// with the exact size of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let row = SdsString::join(["id", "name", "email"], ",");
+    /// assert_eq!(row.as_bytes(), b"id,name,email");
+    /// ```
+    pub fn join<I>(parts: I, sep: impl AsRef<[u8]>) -> SdsString
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let parts = parts.into_iter().collect::<Vec<_>>();
+        let sep = sep.as_ref();
+        let len = parts.iter().map(|part| part.as_ref().len()).sum::<usize>()
+            + sep.len() * parts.len().saturating_sub(1);
+        let mut s = SdsString::with_capacity(len);
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                s.extend_from_slice(sep);
+            }
+            s.extend_from_slice(part.as_ref());
+        }
+        s
+    }
+
+    /// Concatenates `parts`, like [`SdsString::join`] without a separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let s = SdsString::concat([&b"foo"[..], b"\0", b"bar"]);
+    /// assert_eq!(s.as_bytes(), b"foo\0bar");
+    /// ```
+    pub fn concat<I>(parts: I) -> SdsString
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        Self::join(parts, [])
+    }
+
+    /// Joins SDS strings with `sdsjoinsds`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{SdsString, SdsVec};
+    ///
+    /// let words = SdsVec::split(b"a b c", b" ");
+    /// assert_eq!(SdsString::join_sds(&words, b", ").as_bytes(), b"a, b, c");
+    /// ```
+    pub fn join_sds(parts: &[SdsString], sep: &[u8]) -> SdsString {
+        let argc = c_int::try_from(parts.len()).expect("too many parts");
+        let len = parts.iter().map(|part| part.len()).sum::<usize>()
+            + sep.len() * parts.len().saturating_sub(1);
+        // sdsjoinsds takes `sds *` but only reads the strings.
+        let s = unsafe {
+            sdsjoinsds(
+                parts.as_ptr().cast_mut().cast(),
+                argc,
+                sep.as_ptr().cast(),
+                sep.len(),
+            )
+        };
+        SdsString::from_new(s, len)
+    }
+
+    /// Joins C strings with `sdsjoin`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let s = SdsString::join_c_strs(&[c"GET", c"key"], c" ");
+    /// assert_eq!(s.as_bytes(), b"GET key");
+    /// ```
+    pub fn join_c_strs(parts: &[&CStr], sep: &CStr) -> SdsString {
+        let argc = c_int::try_from(parts.len()).expect("too many parts");
+        let mut argv = parts
+            .iter()
+            .map(|part| part.as_ptr().cast_mut())
+            .collect::<Vec<_>>();
+        let len = parts.iter().map(|part| part.count_bytes()).sum::<usize>()
+            + sep.count_bytes() * parts.len().saturating_sub(1);
+        // sdsjoin takes `char **` and `char *` but only reads them.
+        let s = unsafe { sdsjoin(argv.as_mut_ptr(), argc, sep.as_ptr().cast_mut()) };
+        SdsString::from_new(s, len)
+    }
+}
+
+impl<'a> FromIterator<&'a [u8]> for SdsString {
+    fn from_iter<I: IntoIterator<Item = &'a [u8]>>(iter: I) -> Self {
+        Self::concat(iter)
+    }
+}
+
+impl FromIterator<SdsString> for SdsString {
+    fn from_iter<I: IntoIterator<Item = SdsString>>(iter: I) -> Self {
+        Self::concat(iter)
+    }
+}
+
+impl FromIterator<u8> for SdsString {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut s = SdsString::new("");
+        s.extend(iter);
+        s
+    }
+}
+
+impl AsRef<[u8]> for SdsString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Extend<u8> for SdsString {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl<'a> Extend<&'a u8> for SdsString {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a> Extend<&'a [u8]> for SdsString {
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+        for bytes in iter {
+            self.extend_from_slice(bytes);
+        }
+    }
+}
+
+/// Appends to the string, like `sdscatlen`.
+///
+/// # Examples
+///
+/// ```
+/// use sds::SdsString;
+///
+/// let mut s = SdsString::new("foo") + "bar";
+/// s += &b"\0baz"[..];
+/// assert_eq!(s.as_bytes(), b"foobar\0baz");
+/// ```
+impl Add<&[u8]> for SdsString {
+    type Output = SdsString;
+
+    fn add(mut self, rhs: &[u8]) -> SdsString {
+        self.extend_from_slice(rhs);
+        self
+    }
+}
+
+impl Add<&str> for SdsString {
+    type Output = SdsString;
+
+    fn add(self, rhs: &str) -> SdsString {
+        self + rhs.as_bytes()
+    }
+}
+
+impl Add<&SdsStr> for SdsString {
+    type Output = SdsString;
+
+    fn add(self, rhs: &SdsStr) -> SdsString {
+        self + rhs.as_bytes()
+    }
+}
+
+impl AddAssign<&[u8]> for SdsString {
+    fn add_assign(&mut self, rhs: &[u8]) {
+        self.extend_from_slice(rhs);
+    }
+}
+
+impl AddAssign<&str> for SdsString {
+    fn add_assign(&mut self, rhs: &str) {
+        self.extend_from_slice(rhs.as_bytes());
+    }
+}
+
+impl AddAssign<&SdsStr> for SdsString {
+    fn add_assign(&mut self, rhs: &SdsStr) {
+        self.extend_from_slice(rhs.as_bytes());
+    }
+}
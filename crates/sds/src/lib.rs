@@ -2,36 +2,87 @@
 //!
 //!
 
+mod capacity;
+mod cursor;
+mod format;
+mod header;
+mod join;
+mod map;
+mod modify;
+mod num;
+mod read;
+mod split;
+mod trim;
+mod vec;
+mod write;
+
+pub use capacity::TryReserveError;
+pub use cursor::{SdsCursor, SdsStringCursor};
+pub use format::format;
+#[doc(hidden)]
+pub use format::private as __private;
+#[cfg(sds_c_library)]
+pub use format::CatFmtArg;
+pub use header::{InvalidSdsError, SdsHeader, SdsType};
+pub use map::ByteMap;
+pub use modify::Drain;
+pub use num::{ParseNumError, ParseNumErrorKind};
 pub use sds_sys::sds as c_sds;
-use sds_sys::{
-    sdsAllocPtr, sdsAllocSize, sdsIncrLen, sdsMakeRoomFor, sdsRemoveFreeSpace, sdsResize, sds_free,
-    sds_malloc, sds_realloc, sdsalloc, sdsavail, sdscat, sdscatfmt, sdscatlen, sdscatprintf,
-    sdscatrepr, sdscatsds, sdscatvprintf, sdsclear, sdscmp, sdscpy, sdscpylen, sdsdup, sdsempty,
-    sdsfree, sdsfreesplitres, sdsfromlonglong, sdsgrowzero, sdsinclen, sdsjoin, sdsjoinsds, sdslen,
-    sdsmapchars, sdsneedsrepr, sdsnew, sdsnewlen, sdsrange, sdssetalloc, sdssetlen, sdssplitargs,
-    sdssplitlen, sdssubstr, sdstolower, sdstoupper, sdstrim, sdsupdatelen,
-};
+use sds_sys::{sdsdup, sdsfree, sdslen, sdsnewlen, SDS_TYPE_8};
+pub use split::{SplitArgs, SplitArgsError, SplitArgsErrorKind};
 use std::{
     borrow::{Borrow, Cow},
-    ffi::{
-        c_char, c_void, CStr, CString, FromBytesUntilNulError, FromBytesWithNulError, OsStr,
-        OsString,
-    },
+    ffi::{c_char, c_void, CStr},
     fmt::{Debug, Display},
+    hash::{Hash, Hasher},
     mem::ManuallyDrop,
-    ops::{Deref, Index, RangeFrom},
+    ops::{Deref, DerefMut},
     str::Utf8Error,
-    sync::Arc,
 };
+pub use vec::SdsVec;
+
+/// A variant of sds.c, see the `antirez` and `redis7` features of sds-sys.
+///
+/// They lay out strings the same way but don't all have the same functions,
+/// methods that need one the linked library lacks are left out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum Flavor {
+    /// [jcorporation/sds](https://github.com/jcorporation/sds), the default
+    /// and what the `pure-rust` port implements.
+    Jcorporation,
+    /// [antirez/sds](https://github.com/antirez/sds) 2.0, which has no
+    /// `sdsResize`, `sdssubstr` or `sdsneedsrepr`.
+    Antirez,
+    /// `src/sds.c` of Redis 7.2, whose `sdsResize` and `sdsRemoveFreeSpace`
+    /// take an extra `would_regrow` flag.
+    Redis7,
+}
+
+/// The [`Flavor`] of the SDS library this crate was built against.
+#[cfg(sds_flavor = "jcorporation")]
+pub const FLAVOR: Flavor = Flavor::Jcorporation;
+/// The [`Flavor`] of the SDS library this crate was built against.
+#[cfg(sds_flavor = "antirez")]
+pub const FLAVOR: Flavor = Flavor::Antirez;
+/// The [`Flavor`] of the SDS library this crate was built against.
+#[cfg(sds_flavor = "redis7")]
+pub const FLAVOR: Flavor = Flavor::Redis7;
 
 /// Representation of a borrowed C SDS string.
 ///
 /// This type represents a borrowed reference to a length prefixed array of
-/// bytes. It can be constructed safely from a <code>&[[u8]]</code> slice or
-/// unsafely from a raw valid **SDS-created** [`c_sds`] (alias for <code>*const [c_char]</code>).
+/// bytes. It is constructed unsafely, from a raw valid **SDS-created**
+/// [`c_sds`] (alias for <code>*mut [c_char]</code>) with [`SdsStr::from_ptr`]
+/// or from the bytes of one with [`SdsStr::from_bytes_with_nul`].
 ///
 /// The [`SdsStr`] can then be converted to a Rust <code>&[str]</code> by performing UTF-8 validation, or into an owned [`SdsString`].
 ///
+/// An <code>&SdsStr</code> always points at the first byte of a real SDS
+/// string, right after its header, so [`SdsStr::as_ptr`] can be handed back
+/// to libsds. That is also why there is no `Box<SdsStr>` and no way to slice
+/// one: only libsds can allocate or free them.
+///
 /// <code>&SdsStr</code> is to [`SdsString`] as <code>&[str]</code> is to <code>String</code>: the former in each pair are borrowed references; the later are owned strings.
 ///
 /// # Examples
@@ -42,44 +93,253 @@ use std::{
 /// # use std::ffi::c_int;
 ///
 /// pub extern "C" fn count_nuls(s: c_sds) -> c_int {
-///    let s = unsafe { SdsStr::from_raw(s) };
-///    s.iter().filter(|&&c| c == b'\0').count() as c_int;
+///    let s = unsafe { SdsStr::from_ptr(s) };
+///    s.as_bytes().iter().filter(|&&c| c == b'\0').count() as c_int
 /// }
 /// ```
-#[derive(Hash)]
-#[cfg_attr(not(doc), repr(transparent))]
-pub struct SdsStr(c_sds);
+#[repr(transparent)]
+pub struct SdsStr {
+    // The contents followed by the nul terminator. The SDS header lives right
+    // before the first byte.
+    inner: [c_char],
+}
 
 impl SdsStr {
-    pub const unsafe fn from_ptr<'a>(ptr: c_sds) -> &'a Self {}
+    /// Wraps a raw SDS string with a safe SDS string wrapper.
+    ///
+    /// The length is read from the SDS header once, the returned reference
+    /// covers the contents plus the nul terminator.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live string created by libsds (not just any C
+    /// string) that is neither freed nor resized for the lifetime `'a`.
+    ///
+    /// With the `debug-checks` feature, debug builds panic if `ptr` fails the
+    /// checks of [`SdsStr::try_from_ptr`].
+    pub unsafe fn from_ptr<'a>(ptr: c_sds) -> &'a Self {
+        debug_check("SdsStr::from_ptr", ptr);
+        let len = sdslen(ptr);
+        &*(std::ptr::slice_from_raw_parts(ptr, len + 1) as *const Self)
+    }
+
+    /// Like [`SdsStr::from_ptr`], but first checks what can be checked about
+    /// `ptr` without knowing where it came from: that it isn't null, that the
+    /// byte before it is a valid flags byte, that the length in that header
+    /// doesn't exceed the capacity and that there is a nul terminator at the
+    /// end.
+    ///
+    /// Passing these checks doesn't prove that `ptr` is an SDS string, but a
+    /// plain C string is very likely to fail them.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or readable from the start of the header that the
+    /// byte before it describes, up to the nul terminator. If the checks pass
+    /// the requirements of [`SdsStr::from_ptr`] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{InvalidSdsError, SdsStr, SdsString};
+    ///
+    /// let s = SdsString::new("foo");
+    /// assert_eq!(unsafe { SdsStr::try_from_ptr(s.as_ptr().cast_mut()) }, Ok(&*s));
+    ///
+    /// assert_eq!(
+    ///     unsafe { SdsStr::try_from_ptr(std::ptr::null_mut()) },
+    ///     Err(InvalidSdsError::Null)
+    /// );
+    ///
+    /// // A C string preceded by an arbitrary byte.
+    /// let mut buf = *b"\xffhi\0";
+    /// let ptr = unsafe { buf.as_mut_ptr().add(1).cast() };
+    /// assert_eq!(
+    ///     unsafe { SdsStr::try_from_ptr(ptr) },
+    ///     Err(InvalidSdsError::UnknownType { flags: 0xff })
+    /// );
+    ///
+    /// // An otherwise valid sdshdr8 with a bit set above the type bits.
+    /// let mut buf = *b"\x02\x02\x09hi\0";
+    /// let ptr = unsafe { buf.as_mut_ptr().add(3).cast() };
+    /// assert_eq!(
+    ///     unsafe { SdsStr::try_from_ptr(ptr) },
+    ///     Err(InvalidSdsError::UnknownType { flags: 0x09 })
+    /// );
+    /// ```
+    pub unsafe fn try_from_ptr<'a>(ptr: c_sds) -> Result<&'a Self, InvalidSdsError> {
+        let len = header::check(ptr)?;
+        Ok(&*(std::ptr::slice_from_raw_parts(ptr, len + 1) as *const Self))
+    }
+
+    /// Mutable version of [`SdsStr::from_ptr`], for functions that change a
+    /// string in place without resizing it.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`SdsStr::from_ptr`], and nothing else may access the
+    /// string for the lifetime `'a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sds::{SdsStr, c_sds};
+    /// pub extern "C" fn rot13(s: c_sds) {
+    ///     let s = unsafe { SdsStr::from_ptr_mut(s) };
+    ///     for c in s.as_bytes_mut() {
+    ///         match *c {
+    ///             b'a'..=b'z' => *c = (*c - b'a' + 13) % 26 + b'a',
+    ///             b'A'..=b'Z' => *c = (*c - b'A' + 13) % 26 + b'A',
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub unsafe fn from_ptr_mut<'a>(ptr: c_sds) -> &'a mut Self {
+        debug_check("SdsStr::from_ptr_mut", ptr);
+        let len = sdslen(ptr);
+        &mut *(std::ptr::slice_from_raw_parts_mut(ptr, len + 1) as *mut Self)
+    }
+
+    /// Gets the SDS string whose contents and nul terminator are `bytes`,
+    /// checking that it is nul terminated and that the length in the header
+    /// matches.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must start right after the header of a live SDS string, like
+    /// the slices returned by [`SdsStr::as_bytes_with_nul`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{SdsStr, SdsString};
+    ///
+    /// let s = SdsString::new("foo");
+    /// let bytes = s.as_bytes_with_nul();
+    /// assert_eq!(unsafe { SdsStr::from_bytes_with_nul(bytes) }, Ok(&*s));
+    /// assert!(unsafe { SdsStr::from_bytes_with_nul(&bytes[..3]) }.is_err());
+    /// ```
+    pub unsafe fn from_bytes_with_nul(bytes: &[u8]) -> Result<&Self, FromBytesWithNulError> {
+        match bytes.last() {
+            Some(0) => {}
+            _ => return Err(FromBytesWithNulError::NotNulTerminated),
+        }
+        let len = sdslen(bytes.as_ptr().cast_mut().cast());
+        if len != bytes.len() - 1 {
+            return Err(FromBytesWithNulError::LengthMismatch { len });
+        }
+        Ok(Self::from_bytes_with_nul_unchecked(bytes))
+    }
 
-    pub const fn from_bytes_until_nul(bytes: &[u8]) -> Result<&Self, FromBytesUntilNulError> {}
+    /// Unsafely gets the SDS string whose contents and nul terminator are
+    /// `bytes`, without any checks.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be exactly the contents and nul terminator of a live SDS
+    /// string.
+    pub const unsafe fn from_bytes_with_nul_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes as *const [u8] as *const Self)
+    }
 
-    pub const fn from_bytes_with_nul(bytes: &[u8]) -> Result<&Self, FromBytesWithNulError> {}
+    /// Returns the inner pointer to this SDS string, which can be passed to
+    /// the functions of `sds.h` that only read it, like `sdslen` or
+    /// `sdsdup`. Use [`SdsStr::as_mut_ptr`] for the ones that write to it.
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.inner.as_ptr()
+    }
 
-    pub const unsafe fn from_bytes_with_nul_unchecked(bytes: &[u8]) -> &Self {}
+    /// Returns the inner pointer to this SDS string for functions of `sds.h`
+    /// that modify it in place without resizing it, like `sdsmapchars`.
+    pub fn as_mut_ptr(&mut self) -> c_sds {
+        self.inner.as_mut_ptr()
+    }
 
-    pub const fn as_ptr(&self) -> c_sds {}
+    /// Returns the length of the string in bytes, without the nul terminator.
+    pub const fn len(&self) -> usize {
+        self.inner.len() - 1
+    }
 
-    pub const fn len(&self) -> usize {}
+    /// Returns `true` if the string has a length of 0.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-    pub const fn is_empty(&self) -> bool {}
+    /// Converts this SDS string to a byte slice, without the nul terminator.
+    pub const fn as_bytes(&self) -> &[u8] {
+        let bytes = self.as_bytes_with_nul();
+        // SAFETY: There is always a nul terminator.
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr(), bytes.len() - 1) }
+    }
 
-    pub const fn as_bytes(&self) -> &[u8] {}
+    /// Converts this SDS string to a mutable byte slice, without the nul
+    /// terminator.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.len();
+        // SAFETY: c_char and u8 have the same layout.
+        unsafe { &mut *(&mut self.inner[..len] as *mut [c_char] as *mut [u8]) }
+    }
 
-    pub fn as_bytes_mut(&mut self) -> &mut [u8] {}
+    /// Converts this SDS string to a byte slice including the nul terminator.
+    pub const fn as_bytes_with_nul(&self) -> &[u8] {
+        // SAFETY: c_char and u8 have the same layout.
+        unsafe { &*(&self.inner as *const [c_char] as *const [u8]) }
+    }
 
-    pub const fn as_bytes_with_nul(&self) -> &[u8] {}
+    /// Yields a <code>&[str]</code> slice if the SDS string contains valid
+    /// UTF-8.
+    pub const fn to_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(self.as_bytes())
+    }
 
-    pub fn as_bytes_with_nul_mut(&mut self) -> &mut [u8] {}
+    /// Converts an SDS string into a <code>[Cow]<[str]></code>, replacing
+    /// invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
 
-    pub const fn to_str(&self) -> Result<&str, Utf8Error> {}
+    /// Copies this SDS string into a new, owned [`SdsString`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsStr;
+    ///
+    /// let empty: &SdsStr = Default::default();
+    /// assert!(empty.is_empty());
+    /// assert_eq!(empty.to_sds_string().as_bytes_with_nul(), b"\0");
+    /// ```
+    pub fn to_sds_string(&self) -> SdsString {
+        SdsString(unsafe { sdsdup(self.as_ptr().cast_mut()) })
+    }
 
-    pub fn to_string_lossy(&self) -> Cow<'_, str> {}
+    /// Copies this SDS string into a new, owned [`SdsString`].
+    ///
+    /// An `SdsStr` is only ever borrowed, there is nothing to convert without
+    /// copying.
+    #[deprecated(since = "0.1.0", note = "use `to_sds_string`, which does the same")]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_sds_string(&self) -> SdsString {
+        self.to_sds_string()
+    }
+}
 
-    pub fn into_sds_string(self: Box<Self>) -> SdsString {}
+/// Panics if `ptr` fails [`SdsStr::try_from_ptr`], with the `debug-checks`
+/// feature in debug builds.
+#[inline]
+unsafe fn debug_check(function: &str, ptr: c_sds) {
+    if cfg!(all(feature = "debug-checks", debug_assertions)) {
+        if let Err(error) = header::check(ptr) {
+            panic!("{function} called with an invalid SDS string: {error}");
+        }
+    }
 }
 
+/// An empty SDS string that is never freed, for [`Default`]: an
+/// `sdshdr8` with a length and capacity of 0 followed by the nul terminator.
+static EMPTY: [c_char; 4] = [0, 0, SDS_TYPE_8 as c_char, 0];
+
 impl AsRef<SdsStr> for SdsStr {
     fn as_ref(&self) -> &SdsStr {
         self
@@ -98,10 +358,6 @@ impl Borrow<SdsStr> for SdsString {
     }
 }
 
-impl Clone for Box<SdsStr> {
-    fn clone(&self) -> Self {}
-}
-
 impl Debug for SdsStr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "\"{}\"", self.as_bytes().escape_ascii())
@@ -110,68 +366,59 @@ impl Debug for SdsStr {
 
 impl Default for &SdsStr {
     fn default() -> Self {
-        thread_local! {
-            static EMPTY: c_sds = unsafe { sdsempty() };
-        }
+        // SAFETY: EMPTY is a valid SDS string that lives forever.
+        unsafe { SdsStr::from_ptr(EMPTY.as_ptr().add(3).cast_mut()) }
     }
 }
 
-impl Default for Box<SdsStr> {
-    fn default() -> Self {}
-}
-
-impl From<&SdsStr> for Arc<SdsStr> {
-    fn from(value: &SdsStr) -> Self {}
-}
-
-impl From<&SdsStr> for Box<SdsStr> {
-    fn from(value: &SdsStr) -> Self {}
-}
-
 impl From<&SdsStr> for SdsString {
-    fn from(value: &SdsStr) -> Self {}
+    fn from(value: &SdsStr) -> Self {
+        value.to_sds_string()
+    }
 }
 
 impl<'a> From<&'a SdsStr> for Cow<'a, SdsStr> {
-    fn from(value: &'a SdsStr) -> Self {}
-}
-
-impl From<&SdsStr> for Rc<SdsStr> {
-    fn from(value: &SdsStr) -> Self {}
-}
-
-impl From<SdsString> for Box<SdsStr> {
-    fn from(value: SdsString) -> Self {}
+    fn from(value: &'a SdsStr) -> Self {
+        Cow::Borrowed(value)
+    }
 }
 
-impl From<Cow<'_, SdsStr>> for Box<SdsStr> {
-    fn from(value: Cow<'_, SdsStr>) -> Self {}
+impl AsRef<[u8]> for SdsStr {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
 }
 
-impl Index<RangeFrom<usize>> for SdsStr {
-    type Output = SdsStr;
-
-    fn index(&self, index: RangeFrom<usize>) -> &Self::Output {}
+impl Hash for SdsStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
 }
 
 impl Ord for SdsStr {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {}
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
 }
 
 impl PartialEq for SdsStr {
-    fn eq(&self, other: &Self) -> bool {}
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
 }
 
 impl PartialOrd for SdsStr {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {}
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl ToOwned for SdsStr {
     type Owned = SdsString;
 
-    fn to_owned(&self) -> Self::Owned {}
-
-    fn clone_into(&self, target: &mut Self::Owned) {}
+    fn to_owned(&self) -> Self::Owned {
+        SdsString::from(self)
+    }
 }
 
 impl Eq for SdsStr {}
@@ -249,6 +496,7 @@ impl Eq for SdsStr {}
 /// the documentation of [`SdsString`] before use, as improper ownership
 /// management of [`SdsString`] instances can lead to invalid memory accesses,
 /// memory leaks, and other memory errors.
+#[repr(transparent)]
 pub struct SdsString(c_sds);
 
 impl SdsString {
@@ -316,9 +564,40 @@ impl SdsString {
     /// }
     /// ```
     pub unsafe fn from_raw(ptr: *mut c_char) -> Self {
+        debug_check("SdsString::from_raw", ptr);
         Self(ptr)
     }
 
+    /// Like [`SdsString::from_raw`], but first checks `ptr` like
+    /// [`SdsStr::try_from_ptr`]. On error the pointer is left alone.
+    ///
+    /// # Safety
+    ///
+    /// The requirements of [`SdsStr::try_from_ptr`] for the checks, and of
+    /// [`SdsString::from_raw`] if they pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{InvalidSdsError, SdsString};
+    ///
+    /// let raw = SdsString::new("foo").into_raw();
+    /// let s = unsafe { SdsString::try_from_raw(raw) }.unwrap();
+    /// assert_eq!(s.as_bytes(), b"foo");
+    ///
+    /// // An sdshdr8 whose length exceeds its capacity.
+    /// let mut buf = *b"\x05\x02\x01hi\0";
+    /// let ptr = unsafe { buf.as_mut_ptr().add(3).cast() };
+    /// assert_eq!(
+    ///     unsafe { SdsString::try_from_raw(ptr) }.err(),
+    ///     Some(InvalidSdsError::LenExceedsAlloc { len: 5, alloc: 2 })
+    /// );
+    /// ```
+    pub unsafe fn try_from_raw(ptr: *mut c_char) -> Result<Self, InvalidSdsError> {
+        header::check(ptr)?;
+        Ok(Self(ptr))
+    }
+
     /// Consumes the [`SdsString`] and transfers ownership of the string to a C
     /// caller.
     ///
@@ -393,13 +672,7 @@ impl SdsString {
     /// assert_eq!(bytes, b"foo".to_vec());
     /// ```
     pub fn into_bytes(self) -> Vec<u8> {
-        let len = unsafe { sdslen(self.0) };
-        let mut bytes = Vec::with_capacity(len);
-        unsafe {
-            bytes.set_len(len);
-            std::ptr::copy_nonoverlapping(self.0 as *const u8, bytes.as_mut_ptr(), len);
-        }
-        bytes
+        self.as_bytes().to_vec()
     }
 
     /// Equivalent to [`SdsString::into_bytes`] except that the returned vector includes the trailing nul terminator.
@@ -414,13 +687,7 @@ impl SdsString {
     /// assert_eq!(bytes, b"foo\0".to_vec());
     /// ```
     pub fn into_bytes_with_nul(self) -> Vec<u8> {
-        let len = unsafe { sdslen(self.0) + 1 };
-        let mut bytes = Vec::with_capacity(len);
-        unsafe {
-            bytes.set_len(len);
-            std::ptr::copy_nonoverlapping(self.0 as *const u8, bytes.as_mut_ptr(), len);
-        }
-        bytes
+        self.as_bytes_with_nul().to_vec()
     }
 
     /// Returns the contents of this [`SdsString`] as a slice of bytes.
@@ -487,21 +754,73 @@ impl SdsString {
     /// )
     /// ```
     pub unsafe fn from_vec_with_nul_unchecked(v: Vec<u8>) -> Self {
-        let v = ManuallyDrop::new(v);
-        Self(sdsnewlen(v.as_ptr() as *const c_void, v.len()))
+        Self(sdsnewlen(v.as_ptr() as *const c_void, v.len() - 1))
     }
 
-    pub fn from_vec_with_nul(v: Vec<u8>) -> Result<Self, ()> {
-        todo!()
+    /// Attempts to convert a <code>[Vec]<[u8]></code> to an [`SdsString`].
+    ///
+    /// The vector must end with a nul byte, which is dropped. Unlike
+    /// [`CString`](std::ffi::CString), interior nul bytes are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sds::SdsString;
+    /// assert_eq!(
+    ///     SdsString::from_vec_with_nul(b"foo\0bar\0".to_vec()).unwrap(),
+    ///     SdsString::new("foo\0bar")
+    /// );
+    /// assert!(SdsString::from_vec_with_nul(b"foo".to_vec()).is_err());
+    /// ```
+    pub fn from_vec_with_nul(v: Vec<u8>) -> Result<Self, FromVecWithNulError> {
+        if v.last() == Some(&0) {
+            Ok(unsafe { Self::from_vec_with_nul_unchecked(v) })
+        } else {
+            Err(FromVecWithNulError { bytes: v })
+        }
     }
 }
 
 impl Deref for SdsString {
     type Target = SdsStr;
 
-    fn deref(&self) -> &Self::Target {}
+    fn deref(&self) -> &Self::Target {
+        unsafe { SdsStr::from_ptr(self.0) }
+    }
+}
+
+impl DerefMut for SdsString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { SdsStr::from_ptr_mut(self.0) }
+    }
 }
 
+impl Drop for SdsString {
+    fn drop(&mut self) {
+        unsafe { sdsfree(self.0) }
+    }
+}
+
+impl Clone for SdsString {
+    fn clone(&self) -> Self {
+        Self(unsafe { sdsdup(self.0) })
+    }
+}
+
+impl Debug for SdsString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl PartialEq for SdsString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for SdsString {}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct IntoStringError {
     inner: SdsString,
@@ -529,3 +848,54 @@ impl Display for IntoStringError {
         Display::fmt(self.description(), f)
     }
 }
+
+/// An error indicating that the vector passed to
+/// [`SdsString::from_vec_with_nul`] doesn't end with a nul byte.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FromVecWithNulError {
+    bytes: Vec<u8>,
+}
+
+impl FromVecWithNulError {
+    /// Returns the bytes that were attempted to convert.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns ownership of the bytes that were attempted to convert.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Display for FromVecWithNulError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("data provided is not nul terminated")
+    }
+}
+
+impl std::error::Error for FromVecWithNulError {}
+
+/// An error indicating that [`SdsStr::from_bytes_with_nul`] was given a slice
+/// that isn't the whole of an SDS string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FromBytesWithNulError {
+    /// The slice doesn't end with a nul byte.
+    NotNulTerminated,
+    /// The header says that the string is `len` bytes long, which doesn't
+    /// match the slice.
+    LengthMismatch { len: usize },
+}
+
+impl Display for FromBytesWithNulError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotNulTerminated => f.write_str("data provided is not nul terminated"),
+            Self::LengthMismatch { len } => {
+                write!(f, "data provided doesn't match the SDS length of {len}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromBytesWithNulError {}
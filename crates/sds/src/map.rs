@@ -0,0 +1,180 @@
+//! Byte translation and ASCII case mapping in place.
+
+use crate::{SdsStr, SdsString};
+use sds_sys::sdsmapchars;
+
+/// A translation table from every byte value to its replacement, built once
+/// and applied with [`SdsStr::map_bytes_with`].
+///
+/// # Examples
+///
+/// ```
+/// use sds::{ByteMap, SdsString};
+///
+/// // Redis key normalization: ':' and '/' both become '.'.
+/// const NORMALIZE: ByteMap = ByteMap::new(b":/", b"..");
+///
+/// let mut key = SdsString::new("user:42/name");
+/// key.map_bytes_with(&NORMALIZE);
+/// assert_eq!(key.as_bytes(), b"user.42.name");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ByteMap([u8; 256]);
+
+impl ByteMap {
+    /// The table that maps every byte to itself.
+    pub const IDENTITY: ByteMap = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            table[i] = i as u8;
+            i += 1;
+        }
+        ByteMap(table)
+    };
+
+    /// Builds the table that replaces `from[i]` with `to[i]`, the same
+    /// mapping as `sdsmapchars`. If a byte appears more than once in `from`
+    /// its first occurrence wins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` and `to` have different lengths.
+    pub const fn new(from: &[u8], to: &[u8]) -> ByteMap {
+        assert!(
+            from.len() == to.len(),
+            "`from` and `to` must have the same length"
+        );
+        let mut table = Self::IDENTITY.0;
+        let mut seen = [false; 256];
+        let mut i = 0;
+        while i < from.len() {
+            let byte = from[i] as usize;
+            if !seen[byte] {
+                table[byte] = to[i];
+                seen[byte] = true;
+            }
+            i += 1;
+        }
+        ByteMap(table)
+    }
+
+    /// Returns what `byte` is replaced with.
+    pub const fn get(&self, byte: u8) -> u8 {
+        self.0[byte as usize]
+    }
+
+    /// Returns the whole table, indexed by byte value.
+    pub const fn as_array(&self) -> &[u8; 256] {
+        &self.0
+    }
+
+    /// Replaces every byte of `bytes` according to the table.
+    pub fn apply(&self, bytes: &mut [u8]) {
+        for byte in bytes {
+            *byte = self.0[*byte as usize];
+        }
+    }
+}
+
+impl Default for ByteMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl SdsStr {
+    /// Replaces every byte that appears in `from` with the byte at the same
+    /// position in `to`, with `sdsmapchars`.
+    ///
+    /// This goes through `from` for every byte of the string, build a
+    /// [`ByteMap`] once for large sets or hot paths.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` and `to` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("hello");
+    /// s.map_bytes(b"ho", b"01");
+    /// assert_eq!(s.as_bytes(), b"0ell1");
+    /// ```
+    pub fn map_bytes(&mut self, from: &[u8], to: &[u8]) {
+        assert_eq!(
+            from.len(),
+            to.len(),
+            "`from` and `to` must have the same length"
+        );
+        unsafe {
+            sdsmapchars(
+                self.as_mut_ptr(),
+                from.as_ptr().cast(),
+                to.as_ptr().cast(),
+                from.len(),
+            )
+        };
+    }
+
+    /// Replaces every byte according to `map`.
+    pub fn map_bytes_with(&mut self, map: &ByteMap) {
+        map.apply(self.as_bytes_mut());
+    }
+
+    /// Converts the string to ASCII lower case in place.
+    ///
+    /// Bytes that aren't ASCII letters are left alone. Unlike `sdstolower`,
+    /// which goes through the locale dependent `tolower`, this doesn't depend
+    /// on `setlocale`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new(b"GET Key\xc4");
+    /// s.make_ascii_lowercase();
+    /// assert_eq!(s.as_bytes(), b"get key\xc4");
+    /// ```
+    pub fn make_ascii_lowercase(&mut self) {
+        self.as_bytes_mut().make_ascii_lowercase();
+    }
+
+    /// Converts the string to ASCII upper case in place, see
+    /// [`SdsStr::make_ascii_lowercase`].
+    pub fn make_ascii_uppercase(&mut self) {
+        self.as_bytes_mut().make_ascii_uppercase();
+    }
+
+    /// Returns a copy of the string in ASCII lower case.
+    pub fn to_ascii_lowercase(&self) -> SdsString {
+        let mut s = self.to_sds_string();
+        s.make_ascii_lowercase();
+        s
+    }
+
+    /// Returns a copy of the string in ASCII upper case.
+    pub fn to_ascii_uppercase(&self) -> SdsString {
+        let mut s = self.to_sds_string();
+        s.make_ascii_uppercase();
+        s
+    }
+
+    /// Checks that the string and `other` are equal when ignoring the case
+    /// of ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// assert!(SdsString::new("Ping").eq_ignore_ascii_case("PING"));
+    /// assert!(!SdsString::new("Ping").eq_ignore_ascii_case("PONG"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: impl AsRef<[u8]>) -> bool {
+        self.as_bytes().eq_ignore_ascii_case(other.as_ref())
+    }
+}
@@ -0,0 +1,347 @@
+//! Editing an [`SdsString`] in place, like a `Vec<u8>`.
+//!
+//! Every method leaves behind a valid SDS string: the length in the header
+//! is updated and the nul terminator rewritten.
+
+use crate::SdsString;
+use sds_sys::{sdscatlen, sdsclear, sdsgrowzero, sdslen};
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::ops::{Bound, Range, RangeBounds};
+
+impl SdsString {
+    /// Appends a byte to the end of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("ab");
+    /// s.push(b'c');
+    /// assert_eq!(s.as_bytes_with_nul(), b"abc\0");
+    /// ```
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends all bytes of `bytes` to the end of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foo");
+    /// s.extend_from_slice(b"\0bar");
+    /// assert_eq!(s.as_bytes(), b"foo\0bar");
+    /// ```
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let len = self.len() + bytes.len();
+        let s = unsafe { sdscatlen(self.0, bytes.as_ptr().cast(), bytes.len()) };
+        self.set_grown(s, len);
+    }
+
+    /// Inserts a byte at position `index`, shifting everything after it to
+    /// the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("ac");
+    /// s.insert(1, b'b');
+    /// assert_eq!(s.as_bytes(), b"abc");
+    /// ```
+    pub fn insert(&mut self, index: usize, byte: u8) {
+        self.insert_slice(index, &[byte]);
+    }
+
+    /// Inserts all bytes of `bytes` at position `index`, shifting everything
+    /// after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("ad");
+    /// s.insert_slice(1, b"bc");
+    /// assert_eq!(s.as_bytes(), b"abcd");
+    /// ```
+    pub fn insert_slice(&mut self, index: usize, bytes: &[u8]) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        self.reserve(bytes.len());
+        unsafe {
+            let p = self.0.cast::<u8>().add(index);
+            std::ptr::copy(p, p.add(bytes.len()), len - index);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), p, bytes.len());
+            self.set_len(len + bytes.len());
+        }
+    }
+
+    /// Removes and returns the byte at position `index`, shifting everything
+    /// after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("abc");
+    /// assert_eq!(s.remove(1), b'b');
+    /// assert_eq!(s.as_bytes_with_nul(), b"ac\0");
+    /// ```
+    pub fn remove(&mut self, index: usize) -> u8 {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {index}) should be < len (is {len})"
+        );
+        let byte = self.as_bytes()[index];
+        self.as_bytes_mut().copy_within(index + 1.., index);
+        unsafe { self.set_len(len - 1) };
+        byte
+    }
+
+    /// Shortens the string to `len` bytes, keeping its capacity. Does nothing
+    /// if the string is already shorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foobar");
+    /// s.truncate(3);
+    /// assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            unsafe { self.set_len(len) };
+        }
+    }
+
+    /// Removes all bytes, keeping the capacity.
+    pub fn clear(&mut self) {
+        unsafe { sdsclear(self.0) };
+    }
+
+    /// Keeps only the bytes for which `f` returns `true`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("a\0b\0c");
+    /// s.retain(|&b| b != 0);
+    /// assert_eq!(s.as_bytes(), b"abc");
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&u8) -> bool) {
+        let bytes = self.as_bytes_mut();
+        let mut kept = 0;
+        for i in 0..bytes.len() {
+            if f(&bytes[i]) {
+                bytes[kept] = bytes[i];
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Removes the bytes in `range` from the string and returns them as an
+    /// iterator.
+    ///
+    /// The bytes are read from the string as the iterator is advanced and
+    /// removed from it when the iterator is dropped, even if it wasn't used
+    /// up. If the iterator is leaked instead, the string is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end or its end
+    /// is greater than the length of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foobar");
+    /// let drained: Vec<u8> = s.drain(1..4).collect();
+    /// assert_eq!(drained, b"oob");
+    /// assert_eq!(s.as_bytes(), b"far");
+    ///
+    /// let mut drain = s.drain(..2);
+    /// assert_eq!(drain.next(), Some(b'f'));
+    /// drop(drain);
+    /// assert_eq!(s.as_bytes(), b"r");
+    ///
+    /// std::mem::forget(s.drain(..));
+    /// assert_eq!(s.as_bytes(), b"r");
+    /// ```
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Drain<'_> {
+        let range = to_range(range, self.len());
+        Drain {
+            string: self,
+            remaining: range.clone(),
+            range,
+        }
+    }
+
+    /// Splits the string in two at `at`: `self` keeps the bytes before it and
+    /// the rest is returned as a new string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foobar");
+    /// let bar = s.split_off(3);
+    /// assert_eq!(s.as_bytes(), b"foo");
+    /// assert_eq!(bar.as_bytes(), b"bar");
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> SdsString {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+        let other = SdsString::new(&self.as_bytes()[at..]);
+        self.truncate(at);
+        other
+    }
+
+    /// Resizes the string to `new_len` bytes, truncating it or filling the
+    /// new bytes with `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("foo");
+    /// s.resize(5, b'!');
+    /// assert_eq!(s.as_bytes(), b"foo!!");
+    /// s.resize(2, b'!');
+    /// assert_eq!(s.as_bytes(), b"fo");
+    /// ```
+    pub fn resize(&mut self, new_len: usize, value: u8) {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+        let s = unsafe { sdsgrowzero(self.0, new_len) };
+        self.set_grown(s, new_len);
+        debug_assert_eq!(unsafe { sdslen(self.0) }, new_len);
+        if value != 0 {
+            self.as_bytes_mut()[len..].fill(value);
+        }
+    }
+}
+
+/// Resolves `range` against a string of `len` bytes, panicking like slice
+/// indexing when it is out of bounds.
+pub(crate) fn to_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    start..end
+}
+
+/// An iterator over the bytes removed by [`SdsString::drain`].
+pub struct Drain<'a> {
+    string: &'a mut SdsString,
+    /// The indices of the bytes that haven't been yielded yet.
+    remaining: Range<usize>,
+    /// The drained range, removed from `string` on drop.
+    range: Range<usize>,
+}
+
+impl Drain<'_> {
+    /// Returns the remaining bytes as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.string.as_bytes()[self.remaining.clone()]
+    }
+}
+
+impl Debug for Drain<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl Iterator for Drain<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let index = self.remaining.next()?;
+        Some(self.string.as_bytes()[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Drain<'_> {
+    fn next_back(&mut self) -> Option<u8> {
+        let index = self.remaining.next_back()?;
+        Some(self.string.as_bytes()[index])
+    }
+}
+
+impl ExactSizeIterator for Drain<'_> {}
+
+impl FusedIterator for Drain<'_> {}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        let Range { start, end } = self.range;
+        let len = self.string.len();
+        self.string.as_bytes_mut().copy_within(end.., start);
+        unsafe { self.string.set_len(len - (end - start)) };
+    }
+}
@@ -0,0 +1,285 @@
+//! Converting numbers to and from SDS strings the way Redis does with
+//! `ll2string`, `ld2string`, `string2ll` and `string2d`, so that counters
+//! stored by one read back the same in the other.
+
+use crate::{SdsStr, SdsString};
+use sds_sys::sdsfromlonglong;
+use std::fmt::Display;
+
+impl SdsString {
+    /// Creates a string holding the decimal representation of `value`, with
+    /// `sdsfromlonglong`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// assert_eq!(SdsString::from_i64(-42).as_bytes(), b"-42");
+    /// assert_eq!(SdsString::from(i64::MIN).as_bytes(), b"-9223372036854775808");
+    /// ```
+    pub fn from_i64(value: i64) -> Self {
+        // At most 20 bytes, for i64::MIN.
+        Self::from_new(unsafe { sdsfromlonglong(value) }, 20)
+    }
+}
+
+impl From<i64> for SdsString {
+    fn from(value: i64) -> Self {
+        Self::from_i64(value)
+    }
+}
+
+/// The decimal representation of `value`, which `sdsfromlonglong` can't hold
+/// above `i64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use sds::SdsString;
+///
+/// assert_eq!(SdsString::from(u64::MAX).as_bytes(), b"18446744073709551615");
+/// ```
+impl From<u64> for SdsString {
+    fn from(mut value: u64) -> Self {
+        let mut buf = [0; 20];
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        Self::new(&buf[start..])
+    }
+}
+
+/// The representation of `value` that Redis's `ld2string` gives in its
+/// default mode: `%.17g`, which round-trips, with infinities written `inf`
+/// and `-inf` and NaN written `nan` on every platform.
+///
+/// # Examples
+///
+/// ```
+/// use sds::SdsString;
+///
+/// assert_eq!(SdsString::from(3.0).as_bytes(), b"3");
+/// assert_eq!(SdsString::from(0.1).as_bytes(), b"0.10000000000000001");
+/// assert_eq!(SdsString::from(1e100).as_bytes(), b"1e+100");
+/// assert_eq!(SdsString::from(-f64::INFINITY).as_bytes(), b"-inf");
+/// ```
+impl From<f64> for SdsString {
+    fn from(value: f64) -> Self {
+        if value.is_nan() {
+            return Self::new("nan");
+        }
+        if value.is_infinite() {
+            return Self::new(if value > 0.0 { "inf" } else { "-inf" });
+        }
+        // %g picks the notation from the exponent after rounding to 17
+        // significant digits, then drops trailing zeros.
+        const PRECISION: i32 = 17;
+        let scientific = format!("{:.*e}", PRECISION as usize - 1, value);
+        let (mantissa, exponent) = scientific.split_once('e').unwrap();
+        let exponent: i32 = exponent.parse().unwrap();
+        if (-4..PRECISION).contains(&exponent) {
+            let fixed = format!("{:.*}", (PRECISION - 1 - exponent) as usize, value);
+            Self::new(trim_fraction(&fixed))
+        } else {
+            let sign = if exponent < 0 { '-' } else { '+' };
+            let exponent = exponent.unsigned_abs();
+            Self::new(format!("{}e{sign}{exponent:02}", trim_fraction(mantissa)))
+        }
+    }
+}
+
+/// Removes the trailing zeros of the fractional part of `s`, and the
+/// decimal point if nothing is left after it.
+fn trim_fraction(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+impl SdsStr {
+    /// Parses the whole string as a decimal `i64`, with the rules of Redis's
+    /// `string2ll`: an optional `-` followed by digits, without leading zeros,
+    /// whitespace or a `+`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is empty, isn't such a number or the
+    /// number doesn't fit in an `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{ParseNumErrorKind, SdsString};
+    ///
+    /// assert_eq!(SdsString::new("-42").parse_i64(), Ok(-42));
+    /// for invalid in ["+1", " 1", "1 ", "01", "-0", "-", "1.0"] {
+    ///     let err = SdsString::new(invalid).parse_i64().unwrap_err();
+    ///     assert_eq!(err.kind(), ParseNumErrorKind::Invalid);
+    /// }
+    /// let err = SdsString::new("9223372036854775808").parse_i64().unwrap_err();
+    /// assert_eq!(err.kind(), ParseNumErrorKind::OutOfRange);
+    /// ```
+    pub fn parse_i64(&self) -> Result<i64, ParseNumError> {
+        let out_of_range = ParseNumError::new(ParseNumErrorKind::OutOfRange);
+        match self.as_bytes() {
+            [b'-', digits @ ..] => 0i64
+                .checked_sub_unsigned(parse_nonzero_digits(digits)?)
+                .ok_or(out_of_range),
+            digits => i64::try_from(parse_digits(digits)?).map_err(|_| out_of_range),
+        }
+    }
+
+    /// Parses the whole string as a decimal `u64`, with the rules of
+    /// [`SdsStr::parse_i64`] minus the `-`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is empty, isn't such a number or the
+    /// number doesn't fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{ParseNumErrorKind, SdsString};
+    ///
+    /// assert_eq!(SdsString::new("18446744073709551615").parse_u64(), Ok(u64::MAX));
+    /// let err = SdsString::new("-1").parse_u64().unwrap_err();
+    /// assert_eq!(err.kind(), ParseNumErrorKind::Invalid);
+    /// ```
+    pub fn parse_u64(&self) -> Result<u64, ParseNumError> {
+        parse_digits(self.as_bytes())
+    }
+
+    /// Parses the whole string as an `f64`, with the rules of Redis's
+    /// `string2d`: what `strtod` accepts, except for leading whitespace, NaN
+    /// and numbers too large or too small to be told apart from infinity or
+    /// zero. Like `strtod`, a `+` is allowed and `inf` and `infinity` are
+    /// accepted in any case. Unlike it, hexadecimal numbers are not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is empty, isn't such a number or the
+    /// number overflows or underflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{ParseNumErrorKind, SdsString};
+    ///
+    /// assert_eq!(SdsString::new("1.5e3").parse_f64(), Ok(1500.0));
+    /// assert_eq!(SdsString::new("-inf").parse_f64(), Ok(f64::NEG_INFINITY));
+    /// for invalid in [" 1", "1 ", "nan", "1,5"] {
+    ///     let err = SdsString::new(invalid).parse_f64().unwrap_err();
+    ///     assert_eq!(err.kind(), ParseNumErrorKind::Invalid);
+    /// }
+    /// for out_of_range in ["1e400", "-1e-400"] {
+    ///     let err = SdsString::new(out_of_range).parse_f64().unwrap_err();
+    ///     assert_eq!(err.kind(), ParseNumErrorKind::OutOfRange);
+    /// }
+    /// ```
+    pub fn parse_f64(&self) -> Result<f64, ParseNumError> {
+        let bytes = self.as_bytes();
+        if bytes.is_empty() {
+            return Err(ParseNumError::new(ParseNumErrorKind::Empty));
+        }
+        let invalid = ParseNumError::new(ParseNumErrorKind::Invalid);
+        // str::parse rejects whitespace anywhere, as string2d does at the
+        // start and strtod leaves it unparsed at the end.
+        let s = std::str::from_utf8(bytes).map_err(|_| invalid)?;
+        let value: f64 = s.parse().map_err(|_| invalid)?;
+        if value.is_nan() {
+            return Err(invalid);
+        }
+        let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+        let overflow = value.is_infinite()
+            && !unsigned.eq_ignore_ascii_case("inf")
+            && !unsigned.eq_ignore_ascii_case("infinity");
+        let underflow = value == 0.0
+            && unsigned
+                .bytes()
+                .take_while(|&b| b != b'e' && b != b'E')
+                .any(|b| matches!(b, b'1'..=b'9'));
+        if overflow || underflow {
+            return Err(ParseNumError::new(ParseNumErrorKind::OutOfRange));
+        }
+        Ok(value)
+    }
+}
+
+/// Parses `0` or a number without a sign or leading zeros.
+fn parse_digits(digits: &[u8]) -> Result<u64, ParseNumError> {
+    match digits {
+        [] => Err(ParseNumError::new(ParseNumErrorKind::Empty)),
+        [b'0'] => Ok(0),
+        digits => parse_nonzero_digits(digits),
+    }
+}
+
+/// Parses a number without a sign or leading zeros, which `string2ll` also
+/// expects after a `-`.
+fn parse_nonzero_digits(digits: &[u8]) -> Result<u64, ParseNumError> {
+    let invalid = ParseNumError::new(ParseNumErrorKind::Invalid);
+    if !matches!(digits.first(), Some(b'1'..=b'9')) {
+        return Err(invalid);
+    }
+    digits.iter().try_fold(0u64, |value, &digit| {
+        if !digit.is_ascii_digit() {
+            return Err(invalid);
+        }
+        value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(u64::from(digit - b'0')))
+            .ok_or(ParseNumError::new(ParseNumErrorKind::OutOfRange))
+    })
+}
+
+/// The error type for [`SdsStr::parse_i64`], [`SdsStr::parse_u64`] and
+/// [`SdsStr::parse_f64`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseNumError {
+    kind: ParseNumErrorKind,
+}
+
+/// What went wrong in a [`ParseNumError`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ParseNumErrorKind {
+    /// The string is empty.
+    Empty,
+    /// The string isn't a number in the accepted syntax.
+    Invalid,
+    /// The number doesn't fit in the type.
+    OutOfRange,
+}
+
+impl ParseNumError {
+    fn new(kind: ParseNumErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Returns what went wrong.
+    pub fn kind(&self) -> ParseNumErrorKind {
+        self.kind
+    }
+}
+
+impl Display for ParseNumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self.kind {
+            ParseNumErrorKind::Empty => "cannot parse number from empty string",
+            ParseNumErrorKind::Invalid => "invalid number",
+            ParseNumErrorKind::OutOfRange => "number out of range",
+        })
+    }
+}
+
+impl std::error::Error for ParseNumError {}
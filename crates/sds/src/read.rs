@@ -0,0 +1,77 @@
+//! Reading into the spare capacity of an [`SdsString`], the Rust version of
+//! `sdsMakeRoomFor` + `read()` + `sdsIncrLen`.
+
+use crate::SdsString;
+use std::io::{self, Read};
+
+/// How much room [`SdsString::read_from`] makes before reading, the same as
+/// the buffer of `std::io::BufReader`.
+const READ_SIZE: usize = 8 * 1024;
+
+impl SdsString {
+    /// Reads once from `reader` into the spare capacity and appends what was
+    /// read, returning how many bytes that is. Makes room for at least 8 KiB
+    /// first if there is less than that.
+    ///
+    /// `reader` is given at most 8 KiB of the spare capacity, which is zeroed
+    /// first since [`Read::read`] takes an initialized buffer. Zeroing all of
+    /// it would cost as much as the capacity on every call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("> ");
+    /// let n = s.read_from(&mut &b"PING\r\n"[..]).unwrap();
+    /// assert_eq!(n, 6);
+    /// assert_eq!(s.as_bytes(), b"> PING\r\n");
+    /// ```
+    pub fn read_from(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        if self.avail() < READ_SIZE {
+            self.reserve(READ_SIZE);
+        }
+        let spare = self.spare_capacity_mut();
+        let len = spare.len().min(READ_SIZE);
+        let spare = &mut spare[..len];
+        let buf = unsafe {
+            spare.as_mut_ptr().write_bytes(0, spare.len());
+            &mut *(spare as *mut [_] as *mut [u8])
+        };
+        let n = reader.read(buf)?;
+        assert!(
+            n <= buf.len(),
+            "reader returned more bytes than it was given"
+        );
+        unsafe { self.advance_len(n) };
+        Ok(n)
+    }
+
+    /// Reads from `reader` until it reaches EOF and appends everything to the
+    /// string, returning the number of bytes read.
+    ///
+    /// Interrupted reads are retried. On any other error the bytes read so
+    /// far are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let data = vec![b'x'; 100_000];
+    /// let mut s = SdsString::new("");
+    /// assert_eq!(s.read_to_end(&mut &data[..]).unwrap(), data.len());
+    /// assert_eq!(s.as_bytes(), data);
+    /// ```
+    pub fn read_to_end(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        let mut total = 0;
+        loop {
+            match self.read_from(reader) {
+                Ok(0) => return Ok(total),
+                Ok(n) => total += n,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Splitting Redis-style command lines into arguments, like `sdssplitargs`.
+
+use crate::SdsString;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::iter::FusedIterator;
+
+impl SdsString {
+    /// Splits `line` into arguments the way `sdssplitargs` (and so
+    /// `redis-cli` and the Redis config file parser) does.
+    ///
+    /// Arguments are separated by whitespace and may be quoted. In double
+    /// quotes `\n`, `\r`, `\t`, `\b`, `\a` and `\xHH` escapes are decoded and
+    /// any other escaped byte stands for itself. In single quotes only `\'`
+    /// is an escape. A closing quote must be followed by whitespace or the end
+    /// of the line.
+    ///
+    /// Unlike `sdssplitargs` the whole slice is parsed, nul bytes are
+    /// ordinary data rather than the end of the line, and a `\x` that isn't
+    /// followed by two hex digits is an error rather than a literal `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::{SdsString, SplitArgsErrorKind};
+    ///
+    /// let args = SdsString::split_args(br#"SET key "hello\x21 world" 'it\'s'"#).unwrap();
+    /// assert_eq!(args, ["SET", "key", "hello! world", "it's"].map(SdsString::new));
+    ///
+    /// let error = SdsString::split_args(br#"SET key "value"x"#).unwrap_err();
+    /// assert_eq!(error.offset(), 15);
+    /// assert_eq!(error.kind(), SplitArgsErrorKind::MissingSpaceAfterQuote);
+    /// ```
+    pub fn split_args(line: &[u8]) -> Result<Vec<SdsString>, SplitArgsError> {
+        Self::split_args_iter(line)
+            .map(|arg| arg.map(SdsString::new))
+            .collect()
+    }
+
+    /// Like [`SdsString::split_args`], but returns an iterator over the
+    /// arguments that borrows from `line` wherever no escapes had to be
+    /// decoded.
+    ///
+    /// The iterator stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut args = SdsString::split_args_iter(br#"GET "a\nb" c"#);
+    /// assert!(matches!(args.next(), Some(Ok(Cow::Borrowed(b"GET")))));
+    /// assert!(matches!(args.next(), Some(Ok(Cow::Owned(arg))) if arg == b"a\nb"));
+    /// assert!(matches!(args.next(), Some(Ok(Cow::Borrowed(b"c")))));
+    /// assert!(args.next().is_none());
+    /// ```
+    pub fn split_args_iter(line: &[u8]) -> SplitArgs<'_> {
+        SplitArgs {
+            line,
+            pos: 0,
+            failed: false,
+        }
+    }
+}
+
+/// An iterator over the arguments of a line, see
+/// [`SdsString::split_args_iter`].
+#[derive(Clone, Debug)]
+pub struct SplitArgs<'a> {
+    line: &'a [u8],
+    pos: usize,
+    failed: bool,
+}
+
+/// Whitespace as in C's `isspace()`, which `sdssplitargs` skips between
+/// arguments.
+fn is_space(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | 0x0b | 0x0c | b'\r')
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// An argument that stays a slice of the line for as long as it is made of
+/// consecutive bytes of it.
+struct Token<'a> {
+    line: &'a [u8],
+    start: usize,
+    end: usize,
+    owned: Option<Vec<u8>>,
+}
+
+impl<'a> Token<'a> {
+    fn push_from_line(&mut self, i: usize) {
+        match &mut self.owned {
+            Some(owned) => owned.push(self.line[i]),
+            None if self.start == self.end => (self.start, self.end) = (i, i + 1),
+            None if self.end == i => self.end += 1,
+            None => self.push(self.line[i]),
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.owned
+            .get_or_insert_with(|| self.line[self.start..self.end].to_vec())
+            .push(byte);
+    }
+
+    fn finish(self) -> Cow<'a, [u8]> {
+        match self.owned {
+            Some(owned) => Cow::Owned(owned),
+            None => Cow::Borrowed(&self.line[self.start..self.end]),
+        }
+    }
+}
+
+impl<'a> SplitArgs<'a> {
+    fn at(&self, i: usize) -> Option<u8> {
+        self.line.get(i).copied()
+    }
+
+    fn error(&mut self, offset: usize, kind: SplitArgsErrorKind) -> SplitArgsError {
+        self.failed = true;
+        SplitArgsError { offset, kind }
+    }
+
+    /// Checks that the closing quote at `p` is followed by whitespace or
+    /// nothing at all.
+    fn check_closing_quote(&mut self, p: usize) -> Result<(), SplitArgsError> {
+        match self.at(p + 1) {
+            Some(c) if !is_space(c) => {
+                Err(self.error(p + 1, SplitArgsErrorKind::MissingSpaceAfterQuote))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn parse_arg(&mut self) -> Result<Cow<'a, [u8]>, SplitArgsError> {
+        let mut token = Token {
+            line: self.line,
+            start: 0,
+            end: 0,
+            owned: None,
+        };
+        let mut p = self.pos;
+        // The offset of the opening quote, if in double or single quotes.
+        let mut double_quote = None;
+        let mut single_quote = None;
+        loop {
+            let c = self.at(p);
+            if let Some(quote) = double_quote {
+                match c {
+                    None => return Err(self.error(quote, SplitArgsErrorKind::UnterminatedQuote)),
+                    Some(b'\\') if self.at(p + 1) == Some(b'x') => {
+                        match (
+                            self.at(p + 2).and_then(hex_digit),
+                            self.at(p + 3).and_then(hex_digit),
+                        ) {
+                            (Some(high), Some(low)) => token.push(high * 16 + low),
+                            _ => return Err(self.error(p, SplitArgsErrorKind::InvalidHexEscape)),
+                        }
+                        p += 3;
+                    }
+                    Some(b'\\') if p + 1 < self.line.len() => {
+                        p += 1;
+                        token.push(match self.line[p] {
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'b' => 0x08,
+                            b'a' => 0x07,
+                            other => other,
+                        });
+                    }
+                    Some(b'"') => {
+                        self.check_closing_quote(p)?;
+                        p += 1;
+                        break;
+                    }
+                    Some(_) => token.push_from_line(p),
+                }
+            } else if let Some(quote) = single_quote {
+                match c {
+                    None => return Err(self.error(quote, SplitArgsErrorKind::UnterminatedQuote)),
+                    Some(b'\\') if self.at(p + 1) == Some(b'\'') => {
+                        p += 1;
+                        token.push(b'\'');
+                    }
+                    Some(b'\'') => {
+                        self.check_closing_quote(p)?;
+                        p += 1;
+                        break;
+                    }
+                    Some(_) => token.push_from_line(p),
+                }
+            } else {
+                match c {
+                    None => break,
+                    Some(b' ' | b'\n' | b'\r' | b'\t') => {
+                        p += 1;
+                        break;
+                    }
+                    Some(b'"') => double_quote = Some(p),
+                    Some(b'\'') => single_quote = Some(p),
+                    Some(_) => token.push_from_line(p),
+                }
+            }
+            p += 1;
+        }
+        self.pos = p;
+        Ok(token.finish())
+    }
+}
+
+impl<'a> Iterator for SplitArgs<'a> {
+    type Item = Result<Cow<'a, [u8]>, SplitArgsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        while self.at(self.pos).is_some_and(is_space) {
+            self.pos += 1;
+        }
+        if self.pos == self.line.len() {
+            return None;
+        }
+        Some(self.parse_arg())
+    }
+}
+
+impl FusedIterator for SplitArgs<'_> {}
+
+/// The error type for [`SdsString::split_args`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SplitArgsError {
+    offset: usize,
+    kind: SplitArgsErrorKind,
+}
+
+/// What went wrong in a [`SplitArgsError`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum SplitArgsErrorKind {
+    /// A quote is never closed. The offset is that of the opening quote.
+    UnterminatedQuote,
+    /// A `\x` in double quotes isn't followed by two hex digits. The offset
+    /// is that of the backslash.
+    InvalidHexEscape,
+    /// A closing quote is followed by something other than whitespace. The
+    /// offset is that of the byte after the quote.
+    MissingSpaceAfterQuote,
+}
+
+impl SplitArgsError {
+    /// Returns the offset in the line where the error was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns what went wrong.
+    pub fn kind(&self) -> SplitArgsErrorKind {
+        self.kind
+    }
+}
+
+impl Display for SplitArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self.kind {
+            SplitArgsErrorKind::UnterminatedQuote => "unterminated quote",
+            SplitArgsErrorKind::InvalidHexEscape => "invalid \\x escape",
+            SplitArgsErrorKind::MissingSpaceAfterQuote => {
+                "closing quote must be followed by a space"
+            }
+        };
+        write!(f, "{description} at offset {}", self.offset)
+    }
+}
+
+impl std::error::Error for SplitArgsError {}
@@ -0,0 +1,171 @@
+//! Trimming and ranging an [`SdsString`] in place. The contents move to the
+//! front of the buffer, which keeps its capacity.
+
+use crate::modify::to_range;
+use crate::SdsString;
+use sds_sys::{sdsrange, ssize_t};
+use std::ops::{Range, RangeBounds};
+
+impl SdsString {
+    /// Removes all leading and trailing bytes that are in `cset`.
+    ///
+    /// This is what `sdstrim` does, but that also strips nul bytes (its
+    /// `strchr` matches the terminator of `cset`), so the range is found here
+    /// and kept with `sdsrange`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("xxciaoyyy");
+    /// s.trim_matches(b"xy");
+    /// assert_eq!(s.as_bytes(), b"ciao");
+    ///
+    /// let mut s = SdsString::new("\0 a \0");
+    /// s.trim_matches(b" ");
+    /// assert_eq!(s.as_bytes(), b"\0 a \0");
+    /// ```
+    pub fn trim_matches(&mut self, cset: &[u8]) {
+        let bytes = self.as_bytes();
+        let start = bytes
+            .iter()
+            .position(|c| !cset.contains(c))
+            .unwrap_or(bytes.len());
+        let end = bytes
+            .iter()
+            .rposition(|c| !cset.contains(c))
+            .map_or(start, |end| end + 1);
+        self.keep_range(start..end);
+    }
+
+    /// Removes all leading bytes that are in `cset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("  +OK  ");
+    /// s.trim_start_matches(b" +");
+    /// assert_eq!(s.as_bytes(), b"OK  ");
+    /// ```
+    pub fn trim_start_matches(&mut self, cset: &[u8]) {
+        let bytes = self.as_bytes();
+        let start = bytes
+            .iter()
+            .position(|c| !cset.contains(c))
+            .unwrap_or(bytes.len());
+        self.keep_range(start..);
+    }
+
+    /// Removes all trailing bytes that are in `cset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("PING\r\n");
+    /// s.trim_end_matches(b"\r\n");
+    /// assert_eq!(s.as_bytes(), b"PING");
+    /// ```
+    pub fn trim_end_matches(&mut self, cset: &[u8]) {
+        let end = self
+            .as_bytes()
+            .iter()
+            .rposition(|c| !cset.contains(c))
+            .map_or(0, |end| end + 1);
+        self.truncate(end);
+    }
+
+    /// Removes leading and trailing ASCII whitespace, as defined by
+    /// [`u8::is_ascii_whitespace`].
+    pub fn trim_ascii_whitespace(&mut self) {
+        self.trim_matches(b" \t\n\x0c\r");
+    }
+
+    /// Keeps only the bytes in `range`, with `sdsrange`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end or its end is
+    /// greater than the length of the string. Use
+    /// [`SdsString::keep_range_signed`] for the clamping of `sdsrange`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("Hello World");
+    /// s.keep_range(6..);
+    /// assert_eq!(s.as_bytes(), b"World");
+    /// ```
+    pub fn keep_range(&mut self, range: impl RangeBounds<usize>) {
+        let Range { start, end } = to_range(range, self.len());
+        if start == end {
+            self.clear();
+        } else {
+            // `end - 1` can't be negative here, which sdsrange would count
+            // from the end.
+            unsafe { sdsrange(self.0, start as ssize_t, (end - 1) as ssize_t) };
+        }
+    }
+
+    /// Keeps only the bytes from `start` to `end`, both inclusive, exactly
+    /// like `sdsrange`: negative indices count from the end (-1 is the last
+    /// byte) and out of range indices are clamped. The string becomes empty
+    /// if `start` ends up after `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("Hello World");
+    /// s.keep_range_signed(1, -1);
+    /// assert_eq!(s.as_bytes(), b"ello World");
+    /// s.keep_range_signed(-5, 100);
+    /// assert_eq!(s.as_bytes(), b"World");
+    /// s.keep_range_signed(isize::MIN, isize::MAX);
+    /// assert_eq!(s.as_bytes(), b"World");
+    /// s.keep_range_signed(isize::MAX, isize::MIN);
+    /// assert_eq!(s.as_bytes(), b"");
+    /// ```
+    pub fn keep_range_signed(&mut self, start: isize, end: isize) {
+        // Clamped here rather than by sdsrange, whose `end - start + 1`
+        // overflows for indices far out of range.
+        let len = self.len();
+        let clamp = |idx: isize| match usize::try_from(idx) {
+            Ok(idx) => idx,
+            Err(_) => len.saturating_sub(idx.unsigned_abs()),
+        };
+        let (start, end) = (clamp(start), clamp(end));
+        if len == 0 || start > end || start >= len {
+            self.clear();
+        } else {
+            self.keep_range(start..=end.min(len - 1));
+        }
+    }
+
+    /// Keeps only `len` bytes starting at `start`, with `sdssubstr`. Both are
+    /// clamped to the string.
+    ///
+    /// Not available with the antirez flavor of libsds, which has no
+    /// `sdssubstr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsString;
+    ///
+    /// let mut s = SdsString::new("Hello World");
+    /// s.substr(6, 100);
+    /// assert_eq!(s.as_bytes(), b"World");
+    /// ```
+    #[cfg(not(sds_flavor = "antirez"))]
+    pub fn substr(&mut self, start: usize, len: usize) {
+        unsafe { sds_sys::sdssubstr(self.0, start, len) };
+    }
+}
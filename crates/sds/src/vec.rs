@@ -0,0 +1,238 @@
+//! Arrays of SDS strings as returned by `sdssplitlen` and `sdssplitargs`.
+
+use crate::{c_sds, SdsString};
+use sds_sys::{sds_free, sds_malloc, sdsfreesplitres, sdssplitlen, ssize_t};
+use std::alloc::{handle_alloc_error, Layout};
+use std::ffi::c_int;
+use std::fmt::Debug;
+use std::mem::{size_of, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+
+/// An owned array of SDS strings in the layout libsds uses for split
+/// results: an `sds *` allocated with `sds_malloc` plus a count, freed with
+/// `sdsfreesplitres`.
+///
+/// Derefs to <code>[[SdsString]]</code> and converts to and from
+/// <code>[Vec]<[SdsString]></code>.
+///
+/// # Examples
+///
+/// ```
+/// use sds::{SdsString, SdsVec};
+///
+/// let parts = SdsVec::split(b"a,b,,c", b",");
+/// assert_eq!(parts.len(), 4);
+/// assert_eq!(parts[1].as_bytes(), b"b");
+///
+/// let parts: Vec<SdsString> = parts.into();
+/// assert_eq!(parts, ["a", "b", "", "c"].map(SdsString::new));
+/// ```
+pub struct SdsVec {
+    ptr: *mut c_sds,
+    len: usize,
+}
+
+impl SdsVec {
+    /// Splits `bytes` on every occurrence of `sep` with `sdssplitlen`.
+    ///
+    /// An empty `bytes` gives an empty array, and there is one more string
+    /// than there are separators otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sep` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsVec;
+    ///
+    /// assert!(SdsVec::split(b"", b",").is_empty());
+    /// assert_eq!(SdsVec::split(b",", b",").len(), 2);
+    /// ```
+    pub fn split(bytes: &[u8], sep: &[u8]) -> SdsVec {
+        assert!(!sep.is_empty(), "separator must not be empty");
+        // sdssplitlen returns NULL for empty input, as it does when out of
+        // memory.
+        if bytes.is_empty() {
+            return SdsVec::default();
+        }
+        let len = ssize_t::try_from(bytes.len()).expect("input too long");
+        let seplen = c_int::try_from(sep.len()).expect("separator too long");
+        let mut count: c_int = 0;
+        let ptr = unsafe {
+            sdssplitlen(
+                bytes.as_ptr().cast(),
+                len,
+                sep.as_ptr().cast(),
+                seplen,
+                &mut count,
+            )
+        };
+        if ptr.is_null() {
+            handle_alloc_error(Layout::array::<c_sds>(bytes.len() + 1).unwrap());
+        }
+        SdsVec {
+            ptr,
+            len: count as usize,
+        }
+    }
+
+    /// Takes ownership of an array returned by `sdssplitlen` or
+    /// `sdssplitargs`, or by [`SdsVec::into_raw_parts`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be NULL with a `count` of 0, or an array allocated with
+    /// `sds_malloc` (or the allocator behind it) holding `count` SDS strings
+    /// that are owned by the caller. Nothing else may free them afterwards.
+    pub unsafe fn from_raw_parts(ptr: *mut c_sds, count: c_int) -> SdsVec {
+        SdsVec {
+            ptr,
+            len: count.try_into().expect("negative count"),
+        }
+    }
+
+    /// Gives up ownership of the array, for C code that frees it with
+    /// `sdsfreesplitres(ptr, count)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `INT_MAX` strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sds::SdsVec;
+    ///
+    /// let (ptr, count) = SdsVec::split(b"x y", b" ").into_raw_parts();
+    /// assert_eq!(count, 2);
+    /// unsafe { sds_sys::sdsfreesplitres(ptr, count) };
+    /// ```
+    pub fn into_raw_parts(self) -> (*mut c_sds, c_int) {
+        let count = c_int::try_from(self.len).expect("too many strings for a C int count");
+        let this = ManuallyDrop::new(self);
+        (this.ptr, count)
+    }
+}
+
+impl Deref for SdsVec {
+    type Target = [SdsString];
+
+    fn deref(&self) -> &[SdsString] {
+        if self.ptr.is_null() {
+            return &[];
+        }
+        // SAFETY: SdsString is a transparent wrapper around c_sds.
+        unsafe { std::slice::from_raw_parts(self.ptr.cast(), self.len) }
+    }
+}
+
+impl DerefMut for SdsVec {
+    fn deref_mut(&mut self) -> &mut [SdsString] {
+        if self.ptr.is_null() {
+            return &mut [];
+        }
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.cast(), self.len) }
+    }
+}
+
+impl Drop for SdsVec {
+    fn drop(&mut self) {
+        unsafe { sdsfreesplitres(self.ptr, self.len as c_int) }
+    }
+}
+
+impl Debug for SdsVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Clone for SdsVec {
+    fn clone(&self) -> Self {
+        self.to_vec().into()
+    }
+}
+
+impl PartialEq for SdsVec {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for SdsVec {}
+
+impl Default for SdsVec {
+    fn default() -> Self {
+        SdsVec {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl From<Vec<SdsString>> for SdsVec {
+    fn from(strings: Vec<SdsString>) -> Self {
+        if strings.is_empty() {
+            return SdsVec::default();
+        }
+        let layout = Layout::array::<c_sds>(strings.len()).unwrap();
+        let ptr = unsafe { sds_malloc(layout.size()) }.cast::<c_sds>();
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        let len = strings.len();
+        for (i, s) in strings.into_iter().enumerate() {
+            unsafe { ptr.add(i).write(s.into_raw()) };
+        }
+        SdsVec { ptr, len }
+    }
+}
+
+impl From<SdsVec> for Vec<SdsString> {
+    fn from(strings: SdsVec) -> Self {
+        let strings = ManuallyDrop::new(strings);
+        let vec = (0..strings.len)
+            .map(|i| unsafe { SdsString::from_raw(*strings.ptr.add(i)) })
+            .collect();
+        unsafe { sds_free(strings.ptr.cast()) };
+        vec
+    }
+}
+
+impl FromIterator<SdsString> for SdsVec {
+    fn from_iter<I: IntoIterator<Item = SdsString>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
+impl IntoIterator for SdsVec {
+    type Item = SdsString;
+    type IntoIter = std::vec::IntoIter<SdsString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Vec::from(self).into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SdsVec {
+    type Item = &'a SdsString;
+    type IntoIter = std::slice::Iter<'a, SdsString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut SdsVec {
+    type Item = &'a mut SdsString;
+    type IntoIter = std::slice::IterMut<'a, SdsString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// A size mismatch would make the slice casts above unsound.
+const _: () = assert!(size_of::<SdsString>() == size_of::<c_sds>());
@@ -0,0 +1,66 @@
+//! Formatting and writing into an [`SdsString`], which appends to it.
+
+use crate::SdsString;
+use std::{fmt, io};
+
+/// Appends the formatted text, so `write!` works on an [`SdsString`].
+///
+/// # Examples
+///
+/// ```
+/// use sds::SdsString;
+/// use std::fmt::Write;
+///
+/// let mut s = SdsString::new("$");
+/// write!(s, "{}\r\n", 42).unwrap();
+/// assert_eq!(s.as_bytes(), b"$42\r\n");
+/// ```
+impl fmt::Write for SdsString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        Ok(())
+    }
+}
+
+/// Appends the written bytes. Writes never fail or come up short, running out
+/// of memory aborts like it does for a `Vec<u8>`.
+///
+/// # Examples
+///
+/// ```
+/// use sds::SdsString;
+/// use std::io::Write;
+///
+/// let mut s = SdsString::new("");
+/// s.write_all(b"\0binary\xff").unwrap();
+/// assert_eq!(s.as_bytes(), b"\0binary\xff");
+/// ```
+impl io::Write for SdsString {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let len = bufs.iter().map(|buf| buf.len()).sum();
+        self.reserve(len);
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(len)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1 +1,130 @@
+//! Round trips between C and Rust.
+//!
+//! `tests/cstring/lib.rs` is built as a static library (with the features of
+//! sds-sys that this test was built with, so the same libsds), cbindgen generates
+//! `bindings.h` from it, and `tests/cstring/main.c` is compiled against that
+//! and `sds.h`, linked, run, and its output compared.
+#![cfg(sds_c_library)]
 
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cstring")
+}
+
+fn out_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cstring")
+}
+
+/// Builds `libcstring.a` and returns its path along with the native libraries
+/// it needs, as reported by rustc.
+///
+/// Runs a nested cargo with a target directory of its own, since cargo can't
+/// build a staticlib for our own tests. It gets the sds-sys features of this
+/// build, and inherits the `SDS_*` variables, so that it links the libsds
+/// whose `DEP_SDS_INCLUDE` `main.c` is compiled against.
+fn build_staticlib() -> (PathBuf, Vec<String>) {
+    let target_dir = out_dir().join("target");
+    let features = env!("DEP_SDS_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .map(|feature| format!("sds-sys/{feature}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let output = Command::new(env!("CARGO"))
+        .args(["rustc", "--lib", "--manifest-path"])
+        .arg(fixture_dir().join("Cargo.toml"))
+        .args(["--features", &features])
+        .args(["--target", env!("TARGET"), "--target-dir"])
+        .arg(&target_dir)
+        .args(["--", "--print", "native-static-libs"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "failed to build libcstring.a:\n{stderr}"
+    );
+
+    // Cargo replays the note on fresh builds too.
+    let native_libs = stderr
+        .lines()
+        .find_map(|line| line.split_once("native-static-libs: "))
+        .map(|(_, libs)| libs.split_whitespace().map(String::from).collect())
+        .expect("rustc didn't print native-static-libs");
+    let staticlib = target_dir.join(env!("TARGET")).join("debug/libcstring.a");
+    (staticlib, native_libs)
+}
+
+/// Writes `bindings.h` for `tests/cstring/lib.rs` to `include_dir`.
+fn generate_bindings(include_dir: &Path) {
+    let mut config = sds_bindgen::config();
+    config.include_guard = Some("BINDINGS_H".to_string());
+    config.no_includes = true;
+    sds_bindgen::cbindgen::Builder::new()
+        .with_src(fixture_dir().join("lib.rs"))
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate bindings.h")
+        .write_to_file(include_dir.join("bindings.h"));
+}
+
+/// Compiles and links `tests/cstring/<name>.c`, runs it and returns its
+/// stdout.
+fn run(name: &str) -> Vec<u8> {
+    let out_dir = out_dir();
+    let include_dir = out_dir.join("include");
+    std::fs::create_dir_all(&include_dir).unwrap();
+    generate_bindings(&include_dir);
+    let (staticlib, native_libs) = build_staticlib();
+
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("TARGET"))
+        .opt_level(0)
+        .cargo_metadata(false)
+        .get_compiler();
+    let mut command = compiler.to_command();
+    command
+        .arg(fixture_dir().join(format!("{name}.c")))
+        .arg("-I")
+        .arg(&include_dir);
+    for path in env::split_paths(env!("DEP_SDS_INCLUDE")) {
+        command.arg("-I").arg(path);
+    }
+    command.arg(&staticlib);
+    // Only matters when libsds is a shared library.
+    for path in env::split_paths(env!("DEP_SDS_LIB")).filter(|p| !p.as_os_str().is_empty()) {
+        command
+            .arg("-L")
+            .arg(&path)
+            .arg(format!("-Wl,-rpath,{}", path.display()));
+    }
+    let exe = out_dir.join(name);
+    command.args(&native_libs).arg("-o").arg(&exe);
+    let status = command.status().unwrap();
+    assert!(status.success(), "failed to compile {name}.c");
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(output.status.success(), "{name} failed: {output:?}");
+    output.stdout
+}
+
+#[test]
+fn test_main() {
+    assert_eq!(
+        run("main").escape_ascii().to_string(),
+        b"There are 3 NUL bytes\n\
+          message (21): 'He\0llo Al\0an Tur\0ing!'\n\
+          exclaimed (22): 'He\0llo Al\0an Tur\0ing!!'\n\
+          printf(%s): He\n\
+          data (20): 'He\0llo Alan\0 Turing!'\n\
+          There are 2 NUL bytes\n\
+          grown (33): 'He\0llo Alan\0 Turing! (grown by C)'\n\
+          There are 0 NUL bytes\n"
+            .escape_ascii()
+            .to_string()
+    );
+}
@@ -0,0 +1,35 @@
+//! Functions that `main.c` calls. `bindings.h` is generated from this file.
+
+// Written like the README, C callers can't see `unsafe` anyway.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use sds::{c_sds, SdsStr, SdsString};
+use std::ffi::c_int;
+
+/// Borrows a string owned by C.
+#[no_mangle]
+pub extern "C" fn count_nuls(s: c_sds) -> c_int {
+    let s = unsafe { SdsStr::from_ptr(s) };
+    s.as_bytes().iter().filter(|&&c| c == b'\0').count() as c_int
+}
+
+/// Hands a string owned by Rust over to C, which frees it with `sdsfree`.
+#[no_mangle]
+pub extern "C" fn my_data() -> c_sds {
+    SdsString::new("He\0llo Alan\0 Turing!").into_raw()
+}
+
+/// Takes ownership of a string that C got from `my_data`.
+#[no_mangle]
+pub extern "C" fn my_free(data: c_sds) {
+    drop(unsafe { SdsString::from_raw(data) });
+}
+
+/// Takes ownership of a string created by C and gives a new one back.
+#[no_mangle]
+pub extern "C" fn exclaim(s: c_sds) -> c_sds {
+    let s = unsafe { SdsString::from_raw(s) };
+    let mut bytes = s.into_bytes();
+    bytes.push(b'!');
+    SdsString::new(bytes).into_raw()
+}
@@ -3,6 +3,38 @@
 #include <sds.h>
 #include <bindings.h>
 
+/* Prints the whole string, interior NULs included, unlike printf("%s"). */
+static void print_sds(const char *label, sds s) {
+    printf("%s (%zu): '", label, sdslen(s));
+    fwrite(s, 1, sdslen(s), stdout);
+    printf("'\n");
+}
+
 int main() {
+    /* Borrowing: C owns the string, Rust only reads it. */
+    sds message = sdsnewlen("He\0llo Al\0an Tur\0ing!", 21);
+    printf("There are %d NUL bytes\n", count_nuls(message));
+    print_sds("message", message);
+
+    /* C to Rust and back: Rust frees `message` and allocates the result. */
+    message = exclaim(message);
+    print_sds("exclaimed", message);
+    sdsfree(message);
+
+    /* Rust to C: either side can free it. */
+    sds data = my_data();
+    printf("printf(%%s): %s\n", data);
+    print_sds("data", data);
+    printf("There are %d NUL bytes\n", count_nuls(data));
+    sdsfree(data);
+
+    data = my_data();
+    data = sdscat(data, " (grown by C)");
+    print_sds("grown", data);
+    my_free(data);
 
-}
\ No newline at end of file
+    sds empty = sdsempty();
+    printf("There are %d NUL bytes\n", count_nuls(empty));
+    sdsfree(empty);
+    return 0;
+}
@@ -0,0 +1,67 @@
+//! `SdsString::split_args` against `sdssplitargs` of the linked libsds.
+
+use sds::{SdsStr, SdsString, SplitArgsErrorKind};
+use std::ffi::{c_int, CString};
+
+/// Splits `line` with `sdssplitargs`, `None` if it returns NULL.
+fn c_split_args(line: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let line = CString::new(line).unwrap();
+    unsafe {
+        let mut argc: c_int = 0;
+        let argv = sds_sys::sdssplitargs(line.as_ptr(), &mut argc);
+        if argv.is_null() {
+            return None;
+        }
+        let args = (0..argc as usize)
+            .map(|i| SdsStr::from_ptr(*argv.add(i)).as_bytes().to_vec())
+            .collect();
+        sds_sys::sdsfreesplitres(argv, argc);
+        Some(args)
+    }
+}
+
+#[test]
+fn test_same_as_sdssplitargs() {
+    for line in [
+        &b""[..],
+        b"   ",
+        b"set key value",
+        b"  leading and trailing\t\r\n",
+        b"\x0bvertical\x0ctab",
+        b"mixed\"quoted part\"",
+        b"\"\\x41\\x4a\\n\\r\\t\\b\\a\\\\\\\"\\q\"",
+        b"'single \\' quote' \"\" ''",
+        b"\"unterminated",
+        b"'unterminated",
+        b"\"closed\"trailing",
+        b"'closed'trailing",
+        b"\"ends with backslash\\",
+        b"unquoted\\x41",
+    ] {
+        let rust = SdsString::split_args(line)
+            .ok()
+            .map(|args| args.into_iter().map(SdsString::into_bytes).collect());
+        assert_eq!(rust, c_split_args(line), "{}", line.escape_ascii());
+    }
+}
+
+#[test]
+fn test_errors() {
+    for (line, offset, kind) in [
+        (&b"a \"bc"[..], 2, SplitArgsErrorKind::UnterminatedQuote),
+        (b"a 'bc", 2, SplitArgsErrorKind::UnterminatedQuote),
+        (b"\"a\"b", 3, SplitArgsErrorKind::MissingSpaceAfterQuote),
+        (b"'a'b", 3, SplitArgsErrorKind::MissingSpaceAfterQuote),
+        (b"x \"\\x4g\"", 3, SplitArgsErrorKind::InvalidHexEscape),
+        (b"\"\\x4", 1, SplitArgsErrorKind::InvalidHexEscape),
+    ] {
+        let error = SdsString::split_args(line).unwrap_err();
+        assert_eq!((error.offset(), error.kind()), (offset, kind));
+    }
+}
+
+#[test]
+fn test_nul_bytes_are_data() {
+    let args = SdsString::split_args(b"a\0b \"c\0\"").unwrap();
+    assert_eq!(args, [SdsString::new("a\0b"), SdsString::new("c\0")]);
+}